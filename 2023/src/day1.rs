@@ -1,3 +1,5 @@
+use aoc_runner::{Answer, Error, Input, Solution};
+
 fn parse_to_int(s: &str, allow_names: bool) -> Option<u32> {
     let mut checks: Vec<&str> = Vec::from(["1", "2", "3", "4", "5", "6", "7", "8", "9"]);
     if allow_names {
@@ -25,7 +27,7 @@ fn parse_to_int(s: &str, allow_names: bool) -> Option<u32> {
     None
 }
 
-fn get_calibration_value(s: &str, allow_names: bool) -> u32 {
+fn get_calibration_value(s: &str, allow_names: bool) -> Result<u32, Error> {
     let first: Option<u32> = s
         .char_indices()
         .find_map(|(i, _)| parse_to_int(&s[i..], allow_names));
@@ -35,17 +37,42 @@ fn get_calibration_value(s: &str, allow_names: bool) -> u32 {
         .rev()
         .find_map(|(i, _)| parse_to_int(&s[i..], allow_names));
     match (first, last) {
-        (Some(f), Some(l)) => f * 10 + l,
-        _ => panic!("No first or last int found"),
+        (Some(f), Some(l)) => Ok(f * 10 + l),
+        _ => Err(Error::new("No first or last int found")),
     }
 }
 
-pub fn solve_part1(inputs: Vec<String>) -> u32 {
-    inputs.iter().map(|s| get_calibration_value(s, false)).sum()
+fn sum_calibration_values(inputs: &[String], allow_names: bool) -> Result<u32, Error> {
+    let values = aoc_runner::parse_lines(inputs.iter().map(String::as_str), |s| {
+        get_calibration_value(s, allow_names)
+    })?;
+    Ok(values.iter().sum())
+}
+
+pub fn solve_part1(inputs: &[String]) -> Result<u32, Error> {
+    sum_calibration_values(inputs, false)
+}
+
+pub fn solve_part2(inputs: &[String]) -> Result<u32, Error> {
+    sum_calibration_values(inputs, true)
 }
 
-pub fn solve_part2(inputs: Vec<String>) -> u32 {
-    inputs.iter().map(|s| get_calibration_value(s, true)).sum()
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, Error> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error> {
+        solve_part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Option<Result<Answer, Error>> {
+        Some(solve_part2(parsed).map(Answer::from))
+    }
 }
 
 #[cfg(test)]
@@ -54,47 +81,21 @@ mod tests {
 
     #[test]
     fn test_calibration_value() {
-        assert_eq!(get_calibration_value("1abc2", false), 12);
+        assert_eq!(get_calibration_value("1abc2", false), Ok(12));
     }
 
     #[test]
     fn test_calibration_value_2() {
-        assert_eq!(get_calibration_value("pqr3stu8vwx", false), 38);
+        assert_eq!(get_calibration_value("pqr3stu8vwx", false), Ok(38));
     }
 
     #[test]
     fn test_calibration_value_3() {
-        assert_eq!(get_calibration_value("a1b2c3d4e5f", false), 15);
+        assert_eq!(get_calibration_value("a1b2c3d4e5f", false), Ok(15));
     }
 
     #[test]
     fn test_calibration_value_4() {
-        assert_eq!(get_calibration_value("treb7uchet", false), 77);
-    }
-
-    #[test]
-    fn test_part_1() {
-        let inputs: Vec<String> = Vec::from(["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f", "treb7uchet"])
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        assert_eq!(solve_part1(inputs), 142);
-    }
-
-    #[test]
-    fn test_part_2() {
-        let inputs: Vec<String> = Vec::from([
-            "two1nine",
-            "eightwothree",
-            "abcone2threexyz",
-            "xtwone3four",
-            "4nineeightseven2",
-            "zoneight234",
-            "7pqrstsixteen",
-        ])
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-        assert_eq!(solve_part2(inputs), 281);
+        assert_eq!(get_calibration_value("treb7uchet", false), Ok(77));
     }
 }
@@ -1,3 +1,4 @@
+use aoc_runner::{Answer, Error, Input, Solution};
 use std::collections::HashMap;
 
 #[derive(PartialEq, Eq, Debug, Hash, Copy, Clone)]
@@ -19,7 +20,7 @@ impl Set {
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: u32,
     sets: Vec<Set>,
 }
@@ -43,43 +44,50 @@ impl Game {
     }
 }
 
-fn parse_line(line: &str) -> Game {
-    let chunks: Vec<&str> = line.split(|c| c == ':' || c == ';').collect();
+fn parse_color(line: &str, color: &str) -> Result<(u32, Color), Error> {
+    let pairs: Vec<&str> = color.split_whitespace().collect();
+    let [count, name] = pairs[..] else {
+        return Err(Error::at(line, color, "Expected '<count> <color>'"));
+    };
+    let count = count
+        .parse::<u32>()
+        .map_err(|_| Error::at(line, count, "Invalid count"))?;
+    let color = match name {
+        "blue" => Color::Blue,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        _ => return Err(Error::at(line, name, "Unknown color")),
+    };
+    Ok((count, color))
+}
+
+fn parse_line(line: &str) -> Result<Game, Error> {
+    let chunks: Vec<&str> = line.split([':', ';']).collect();
     let game_id = chunks[0]
         .strip_prefix("Game")
-        .unwrap()
-        .trim()
+        .ok_or_else(|| Error::at(line, line, "Expected 'Game'"))?
+        .trim();
+    let game_id = game_id
         .parse::<u32>()
-        .unwrap();
-    let parsed_sets: Vec<Set> = chunks[1..]
+        .map_err(|_| Error::at(line, game_id, "Invalid game id"))?;
+    let parsed_sets = chunks[1..]
         .iter()
         .map(|set| {
-            let colors: Vec<(u32, Color)> = set
+            let colors = set
                 .split(',')
-                .map(|color| {
-                    let pairs: Vec<&str> = color.split_whitespace().collect();
-                    let count = pairs[0].parse::<u32>().unwrap();
-                    let color = match pairs[1] {
-                        "blue" => Color::Blue,
-                        "red" => Color::Red,
-                        "green" => Color::Green,
-                        _ => panic!("Unknown color"),
-                    };
-                    (count, color)
-                })
-                .collect();
-            Set { colors }
+                .map(|color| parse_color(line, color))
+                .collect::<Result<Vec<(u32, Color)>, Error>>()?;
+            Ok(Set { colors })
         })
-        .collect();
+        .collect::<Result<Vec<Set>, Error>>()?;
 
-    Game {
+    Ok(Game {
         id: game_id,
         sets: parsed_sets,
-    }
+    })
 }
 
-pub fn solve_part1(inputs: Vec<String>) -> u32 {
-    let games: Vec<Game> = inputs.iter().map(|s| parse_line(s)).collect();
+pub fn solve_part1(games: &[Game]) -> u32 {
     let possible = games.iter().filter(|g| {
         g.sets.iter().all(|s| {
             s.colors.iter().all(|c| match c.1 {
@@ -92,11 +100,28 @@ pub fn solve_part1(inputs: Vec<String>) -> u32 {
     possible.map(|g| g.id).sum()
 }
 
-pub fn solve_part2(inputs: Vec<String>) -> u32 {
-    let games: Vec<Game> = inputs.iter().map(|s| parse_line(s)).collect();
+pub fn solve_part2(games: &[Game]) -> u32 {
     games.iter().map(|g| g.minimal_required_set().power()).sum()
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<Game>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, Error> {
+        aoc_runner::parse_lines(input.lines(), parse_line)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error> {
+        Ok(solve_part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Option<Result<Answer, Error>> {
+        Some(Ok(solve_part2(parsed).into()))
+    }
+}
+
 // Test parse line on Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 #[cfg(test)]
 mod tests {
@@ -105,7 +130,7 @@ mod tests {
     #[test]
     fn test_parse_line() {
         let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 66 blue; 2 green";
-        let game = parse_line(line);
+        let game = parse_line(line).unwrap();
         assert_eq!(game.id, 1);
         assert_eq!(game.sets.len(), 3);
         assert_eq!(game.sets[0].colors.len(), 2);
@@ -125,26 +150,11 @@ mod tests {
         assert_eq!(game.sets[2].colors[0].1, Color::Green);
     }
 
-    // test part 1
-    #[test]
-    fn test_d2_part_1() {
-        let s = r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#;
-        let inputs = s.split('\n').map(|s| s.to_string()).collect();
-        assert_eq!(solve_part1(inputs), 8);
-    }
-
     #[test]
-    fn test_d2_part_2() {
-        let s = r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#;
-        let inputs = s.split('\n').map(|s| s.to_string()).collect();
-        assert_eq!(solve_part2(inputs), 2286);
+    fn test_parse_line_unknown_color() {
+        let line = "Game 1: 3 blue; 4 purple";
+        let err = parse_line(line).unwrap_err();
+        assert_eq!(err.column, Some(19));
+        assert_eq!(err.message, "Unknown color");
     }
 }
@@ -1,139 +1,203 @@
-use std::{char, collections::HashMap, fmt, fmt::Display};
+use aoc_runner::{Answer, Error, Grid, Input, Painter, Point, Solution, Span, Style};
+use std::{collections::HashMap, fmt, fmt::Display};
 
 #[derive(Debug)]
-struct Number {
-    values: Vec<u32>,
-    indices: Vec<(i32, i32)>,
+pub struct Number {
+    value: u32,
+    span: Span,
 }
 
 impl Display for Number {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let coords = format!(
-            "({}..{}, {})",
-            self.indices.first().unwrap().1,
-            self.indices.last().unwrap().1,
-            self.indices.first().unwrap().0,
+            "({}..={}, {})",
+            self.span.start.x, self.span.end.x, self.span.start.y,
         );
-        write!(f, "value = {:}, coords = {:}", self.value(), coords)
+        write!(f, "value = {:}, coords = {:}", self.value, coords)
     }
 }
 
 impl Number {
-    fn default() -> Number {
-        Number {
-            values: Vec::new(),
-            indices: Vec::new(),
-        }
+    /// Every cell around the digits inside the grid.
+    fn neighbors<'a>(&self, grid: &'a Grid<char>) -> impl Iterator<Item = Point> + 'a {
+        self.span.neighbors().filter(|&p| grid.contains(p))
     }
+}
 
-    fn value(&self) -> u32 {
-        self.values.iter().fold(0, |acc, d| acc * 10 + d)
-    }
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
 
-    fn empty(&self) -> bool {
-        self.values.is_empty()
-    }
+/// The numbers in the schematic, with the grid they were found in.
+fn find_numbers(grid: Grid<char>) -> Schematic {
+    let numbers = grid
+        .spans(char::is_ascii_digit)
+        .map(|span| Number {
+            value: grid
+                .span(&span)
+                .iter()
+                .fold(0, |acc, c| acc * 10 + c.to_digit(10).unwrap()),
+            span,
+        })
+        .collect();
+    (numbers, grid)
+}
 
-    fn neighbors(&self) -> Vec<(i32, i32)> {
-        let last = self.indices.last().unwrap();
-        let first = self.indices.first().unwrap();
-        let mut neighbors = Vec::from([
-            (first.0 - 1, first.1),     // left
-            (first.0 - 1, first.1 + 1), // top left
-            (first.0 - 1, first.1 - 1), // bottom left
-            (last.0 + 1, last.1),       // right
-            (last.0 + 1, last.1 + 1),   // top right
-            (last.0 + 1, last.1 - 1),   // bottom right
-        ]);
-        // Add top and bottom
-        for (x, y) in self.indices.iter() {
-            neighbors.push((*x, *y + 1)); // top
-            neighbors.push((*x, *y - 1)); // bottom
-        }
-        neighbors
-    }
+type Schematic = (Vec<Number>, Grid<char>);
+
+fn is_part(number: &Number, grid: &Grid<char>) -> bool {
+    number.neighbors(grid).any(|p| is_symbol(grid[p]))
 }
 
-fn parse_line(lines: Vec<String>) -> (Vec<Number>, HashMap<(i32, i32), char>) {
-    let mut symbols: HashMap<(i32, i32), char> = HashMap::new();
-    let mut numbers: Vec<Number> = Vec::new();
-    let mut current_number = Number::default();
-    for (y, row) in lines.iter().enumerate() {
-        for (x, c) in row.chars().enumerate() {
-            if c != '.' && !c.is_ascii_digit() {
-                symbols.insert((x as i32, y as i32), c);
-            }
-            if c.is_ascii_digit() {
-                current_number.values.push(c.to_digit(10).unwrap());
-                current_number.indices.push((x as i32, y as i32));
-            } else if !current_number.empty() {
-                numbers.push(current_number);
-                current_number = Number::default();
+/// Every `*` next to exactly two numbers, with those numbers.
+fn gears(numbers: &[Number], grid: &Grid<char>) -> HashMap<Point, Vec<u32>> {
+    let mut symbol_numbers: HashMap<Point, Vec<u32>> = HashMap::new();
+    for number in numbers {
+        for n in number.neighbors(grid) {
+            if grid[n] == '*' {
+                let current = symbol_numbers.entry(n).or_default();
+                current.push(number.value);
             }
         }
     }
-    (numbers, symbols)
+    symbol_numbers.retain(|_, v| v.len() == 2);
+    symbol_numbers
 }
 
-pub fn solve_part1(inputs: Vec<String>) -> u32 {
-    let (numbers, symbols) = parse_line(inputs);
-    let part_numbers = numbers
-        .iter()
-        .filter(|n| n.neighbors().iter().any(|n| symbols.contains_key(n)));
-    part_numbers.map(|n| n.value()).sum()
+pub fn solve_part1((numbers, grid): &Schematic) -> u32 {
+    let part_numbers = numbers.iter().filter(|n| is_part(n, grid));
+    part_numbers.map(|n| n.value).sum()
 }
 
-pub fn solve_part2(inputs: Vec<String>) -> u32 {
-    let (numbers, symbols) = parse_line(inputs);
-    let mut symbol_numbers: HashMap<(i32, i32), Vec<u32>> = HashMap::new();
+pub fn solve_part2((numbers, grid): &Schematic) -> u32 {
+    gears(numbers, grid).values().map(|v| v[0] * v[1]).sum()
+}
+
+/// The schematic with part numbers in green, other numbers dimmed and gears
+/// in yellow, followed by what was counted. Plain text keeps the schematic
+/// as it is and relies on the lists.
+pub fn visualize((numbers, grid): &Schematic, painter: &Painter) -> String {
+    let mut styles: Grid<Option<Style>> = grid.map(|&c| is_symbol(c).then_some(Style::Bold));
+    let mut not_parts = Vec::new();
     for number in numbers {
-        for coord in number.neighbors() {
-            if symbols.contains_key(&coord) {
-                let current = symbol_numbers.entry(coord).or_default();
-                current.push(number.value());
-            }
+        let style = if is_part(number, grid) {
+            Style::Green
+        } else {
+            not_parts.push(number);
+            Style::Dim
+        };
+        for p in number.span.points() {
+            styles[p] = Some(style);
         }
     }
+    let mut gears: Vec<(Point, Vec<u32>)> = gears(numbers, grid).into_iter().collect();
+    gears.sort_by_key(|&(p, _)| (p.y, p.x));
+    for (p, _) in &gears {
+        styles[*p] = Some(Style::Yellow);
+    }
 
-    symbol_numbers
-        .iter()
-        .filter(|(_, v)| v.len() == 2)
-        .map(|(_, v)| v[0] * v[1])
-        .sum()
+    let mut out = String::new();
+    for (y, row) in grid.rows().enumerate() {
+        let mut x = 0;
+        // Runs of one style, so each gets a single escape sequence
+        while x < row.len() {
+            let style = styles[Point::new(x as i64, y as i64)];
+            let cells = (x..row.len())
+                .take_while(|&i| styles[Point::new(i as i64, y as i64)] == style)
+                .count();
+            let run: String = row[x..x + cells].iter().collect();
+            x += cells;
+            out += &painter.paint_if(run, style);
+        }
+        out.push('\n');
+    }
+
+    let not_parts: Vec<String> = not_parts.iter().map(|n| n.to_string()).collect();
+    out += &format!(
+        "\n{} not part numbers{}{}",
+        painter.paint(not_parts.len(), Style::Dim),
+        if not_parts.is_empty() { "" } else { ":\n  " },
+        not_parts.join("\n  ")
+    );
+    out += &format!("\n{} gears", painter.paint(gears.len(), Style::Yellow));
+    for (p, ratio) in gears {
+        out += &format!(
+            "\n  {}: {} * {} = {}",
+            p,
+            ratio[0],
+            ratio[1],
+            painter.paint(ratio[0] * ratio[1], Style::Yellow)
+        );
+    }
+    out
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Schematic;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, Error> {
+        Ok(find_numbers(Grid::parse(input.lines(), Ok)?))
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error> {
+        Ok(solve_part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Option<Result<Answer, Error>> {
+        Some(Ok(solve_part2(parsed).into()))
+    }
+
+    fn visualize(parsed: &Self::Parsed, painter: &Painter) -> Option<String> {
+        Some(visualize(parsed, painter))
+    }
 }
 
-// Test parse line on Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 #[cfg(test)]
 mod tests {
     use super::*;
-    // test part 1
+
+    fn schematic(text: &str) -> Schematic {
+        Day3::parse(&Input::new(text)).unwrap()
+    }
+
+    #[test]
+    fn test_numbers_end_with_their_row() {
+        // 12 ends in the last column, 34 starts the next row and 7 ends the
+        // grid
+        let parsed = schematic("..12\n34*.\n.#.7");
+        let values: Vec<u32> = parsed.0.iter().map(|n| n.value).collect();
+        assert_eq!(values, [12, 34, 7]);
+        assert_eq!(solve_part1(&parsed), 53);
+    }
+
+    #[test]
+    fn test_gear_at_row_edges() {
+        assert_eq!(solve_part2(&schematic("..12\n34*.")), 408);
+    }
+
+    #[test]
+    fn test_visualize() {
+        let parsed = schematic("..12\n34*.\n5...");
+        let plain = visualize(&parsed, &Painter { ansi: false });
+        assert!(plain.starts_with("..12\n34*.\n5...\n"));
+        assert!(plain.contains("value = 5, coords = (0..=0, 2)"));
+        assert!(plain.contains("(2, 1): 12 * 34 = 408"));
+
+        let ansi = visualize(&parsed, &Painter { ansi: true });
+        assert!(ansi.contains("..\x1b[32m12\x1b[0m\n"));
+        assert!(ansi.contains("\x1b[33m*\x1b[0m"));
+        assert!(ansi.contains("\x1b[2m5\x1b[0m"));
+    }
+
     #[test]
-    fn test_d3_part_1() {
-        let s = r#"467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598.."#;
-        let inputs = s.split('\n').map(|s| s.to_string()).collect();
-        assert_eq!(solve_part1(inputs), 4361);
-    }
-    fn test_d3_part_2() {
-        let s = r#"467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598.."#;
-        let inputs = s.split('\n').map(|s| s.to_string()).collect();
-        assert_eq!(solve_part1(inputs), 467835);
+    fn test_visualize_wide_glyphs() {
+        // Multi-byte symbols take one cell each, like any other
+        let parsed = schematic("é12°\n.5..");
+        let plain = visualize(&parsed, &Painter { ansi: false });
+        assert!(plain.starts_with("é12°\n.5..\n"));
+        let ansi = visualize(&parsed, &Painter { ansi: true });
+        assert!(ansi.starts_with("\x1b[1mé\x1b[0m\x1b[32m12\x1b[0m\x1b[1m°\x1b[0m\n"));
     }
 }
@@ -1,10 +1,12 @@
+use aoc_runner::{Answer, Error, Input, Solution};
 use nom::{
     bytes::complete::tag, character::complete::digit1, character::complete::multispace0,
-    character::complete::multispace1, multi::separated_list1, IResult,
+    character::complete::multispace1, character::complete::u32 as nom_u32,
+    multi::separated_list1, IResult,
 };
 
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     winning_numbers: Vec<u32>,
     my_numbers: Vec<u32>,
 }
@@ -33,13 +35,11 @@ fn parse_line(line: &str) -> IResult<&str, Card> {
     let (i, _card_id) = digit1(i)?;
     let (i, _) = tag(":")(i)?;
     let (i, _) = multispace0(i)?;
-    let (i, winning_numbers) = separated_list1(multispace1, digit1)(i)?;
+    let (i, winning_numbers) = separated_list1(multispace1, nom_u32)(i)?;
     let (i, _) = multispace0(i)?;
-    let winning_numbers: Vec<u32> = winning_numbers.iter().map(|s| s.parse().unwrap()).collect();
     let (i, _) = tag("|")(i)?;
     let (i, _) = multispace0(i)?;
-    let (i, my_numbers) = separated_list1(multispace1, digit1)(i)?;
-    let my_numbers: Vec<u32> = my_numbers.iter().map(|s| s.parse().unwrap()).collect();
+    let (i, my_numbers) = separated_list1(multispace1, nom_u32)(i)?;
 
     Ok((
         i,
@@ -50,50 +50,48 @@ fn parse_line(line: &str) -> IResult<&str, Card> {
     ))
 }
 
-pub fn solve_part1(inputs: Vec<String>) -> u32 {
-    let cards: Vec<Card> = inputs.iter().map(|l| parse_line(l).unwrap().1).collect();
+pub fn solve_part1(cards: &[Card]) -> u32 {
     cards.iter().map(|c| c.points()).sum()
 }
 
-pub fn solve_part2(inputs: Vec<String>) -> u32 {
-    let cards: Vec<Card> = inputs.iter().map(|l| parse_line(l).unwrap().1).collect();
+pub fn solve_part2(cards: &[Card]) -> Result<u32, Error> {
     let mut copies: Vec<u32> = vec![1; cards.len()];
     let mut result: u32 = 0;
 
     for (current_i, card) in cards.iter().enumerate() {
         let n_winners = card.my_winning_numbers().len();
+        if current_i + n_winners >= cards.len() {
+            return Err(Error {
+                line: Some(current_i + 1),
+                ..Error::new("Card wins copies of cards past the end of the table")
+            });
+        }
         for i in 0..n_winners {
             copies[current_i + i + 1] += copies[current_i]
         }
         result += copies[current_i];
     }
-    result
+    Ok(result)
 }
 
-// Test parse line on Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_d4_p1() {
-        let s = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
-        let inputs = s.split('\n').map(|s| s.to_string()).collect();
-        assert_eq!(solve_part1(inputs), 13);
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Vec<Card>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, Error> {
+        aoc_runner::parse_lines(input.lines(), |l| {
+            parse_line(l)
+                .map(|(_, card)| card)
+                .map_err(|e| Error::from_nom(l, e))
+        })
     }
-    #[test]
-    fn test_d4_p2() {
-        let s = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
-        let inputs = s.split('\n').map(|s| s.to_string()).collect();
-        assert_eq!(solve_part2(inputs), 30);
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error> {
+        Ok(solve_part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Option<Result<Answer, Error>> {
+        Some(solve_part2(parsed).map(Answer::from))
     }
 }
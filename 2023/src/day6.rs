@@ -1,101 +1,162 @@
+use aoc_runner::{Answer, Error, Input, Solution};
+
 #[derive(Debug)]
-struct Race {
-    time: f64,
-    distance: f64,
+pub struct Race {
+    time: u64,
+    distance: u64,
 }
 
-fn parse_lines(inputs: Vec<String>) -> Vec<Race> {
-    let times: Vec<f64> = inputs[0]
-        .strip_prefix("Time:")
-        .unwrap()
-        .split_whitespace()
-        .map(|s| s.parse::<f64>().unwrap())
-        .collect();
-    let distances: Vec<f64> = inputs[1]
-        .strip_prefix("Distance:")
-        .unwrap()
+/// The races, and the digits of each line joined together for part 2.
+#[derive(Debug)]
+pub struct Races {
+    races: Vec<Race>,
+    time: String,
+    distance: String,
+}
+
+fn parse_values(lines: &[&str], index: usize, prefix: &str) -> Result<(Vec<u64>, String), Error> {
+    let line = lines
+        .get(index)
+        .ok_or_else(|| Error::new(format!("Missing '{}' line", prefix)))?;
+    let values = line
+        .strip_prefix(prefix)
+        .ok_or_else(|| Error::at(line, line, format!("Expected '{}'", prefix)))?;
+    let parsed = values
         .split_whitespace()
-        .map(|s| s.parse::<f64>().unwrap())
-        .collect();
+        .map(|s| {
+            s.parse::<u64>()
+                .map_err(|_| Error::at(line, s, "Invalid number"))
+        })
+        .collect::<Result<Vec<u64>, Error>>()
+        .map_err(|e| e.on_line(index + 1, line))?;
+    Ok((parsed, values.split_whitespace().collect()))
+}
+
+fn parse_lines(input: &Input) -> Result<Races, Error> {
+    let lines: Vec<&str> = input.lines().collect();
+    let (times, time) = parse_values(&lines, 0, "Time:")?;
+    let (distances, distance) = parse_values(&lines, 1, "Distance:")?;
+    if times.len() != distances.len() {
+        return Err(Error::new(format!(
+            "{} times but {} distances",
+            times.len(),
+            distances.len()
+        ))
+        .on_line(2, lines[1]));
+    }
 
     let races: Vec<Race> = times
         .into_iter()
         .zip(distances)
         .map(|(time, distance)| Race { time, distance })
         .collect();
-    races
+    Ok(Races {
+        races,
+        time,
+        distance,
+    })
 }
 
 // x * (t - x) > d
 // x * t - x^2 > d
 // x^2 - x * t + d < 0
-fn parabola_zeros(a: f64, b: f64, c: f64) -> (f64, f64) {
-    let discriminant = (b.powf(2.0) - 4.0 * a * c).sqrt();
-    let x1: f64 = (-b + discriminant) / (2.0 * a);
-    let x2: f64 = (-b - discriminant) / (2.0 * a);
-    let larger = x1.max(x2);
-    let smaller = x1.min(x2);
-    (smaller, larger)
-}
-
-fn n_wins(time: f64, distance: f64) -> u64 {
-    let (x1, x2) = parabola_zeros(1.0, -time, distance);
-    let x1c = x1.ceil();
-    let x2f = x2.floor();
-    let d1 = x1c * (time - x1);
-    let d2 = x2f * (time - x2);
-
-    // Check the edge case where the zeros are ties - they are not wins
-    let mut n_ties: u64 = 0;
-    if (distance - d1).abs() < 1e-6 {
-        n_ties += 1;
+/// The holds that beat `distance` are symmetric around `time / 2`, from the
+/// first one to `time` minus it. The float root only gives a first guess,
+/// which exact integer checks then settle.
+fn n_wins(time: u64, distance: u64) -> u64 {
+    let beats = |x: u64| x as u128 * (time - x) as u128 > distance as u128;
+    let half = time / 2;
+    if !beats(half) {
+        return 0;
     }
-    if (distance - d2).abs() < 1e-6 {
-        n_ties += 1;
+    let discriminant = (time as f64).powi(2) - 4.0 * distance as f64;
+    let root = (time as f64 - discriminant.max(0.0).sqrt()) / 2.0;
+    let mut first = (root.max(0.0) as u64).min(half);
+    while first > 0 && beats(first - 1) {
+        first -= 1;
     }
-    // +1 because edges are inclusive
-    (x2f - x1c) as u64 - n_ties + 1
+    while !beats(first) {
+        first += 1;
+    }
+    (time + 1).saturating_sub(2 * first)
 }
 
-pub fn solve_part1(inputs: Vec<String>) -> u64 {
-    let races = parse_lines(inputs);
-    races.iter().map(|r| n_wins(r.time, r.distance)).product()
+pub fn solve_part1(races: &Races) -> u64 {
+    races.races.iter().map(|r| n_wins(r.time, r.distance)).product()
 }
 
-pub fn solve_part2(inputs: Vec<String>) -> u64 {
-    let time: f64 = inputs[0]
-        .strip_prefix("Time:")
-        .unwrap()
-        .replace(' ', "")
-        .parse()
-        .unwrap();
-    let dist: f64 = inputs[1]
-        .strip_prefix("Distance:")
-        .unwrap()
-        .replace(' ', "")
+// Part 2 reads each line as one number with the spaces removed
+fn joined(digits: &str, what: &str) -> Result<u64, Error> {
+    if digits.is_empty() {
+        return Err(Error::new(format!("No {} to join", what)));
+    }
+    digits
         .parse()
-        .unwrap();
-    n_wins(time, dist)
+        .map_err(|_| Error::new(format!("Joined {} {} is too large", what, digits)))
+}
+
+pub fn solve_part2(races: &Races) -> Result<u64, Error> {
+    let time = joined(&races.time, "time")?;
+    let dist = joined(&races.distance, "distance")?;
+    Ok(n_wins(time, dist))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed = Races;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, Error> {
+        parse_lines(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error> {
+        Ok(solve_part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Option<Result<Answer, Error>> {
+        Some(solve_part2(parsed).map(Answer::from))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn races(text: &str) -> Races {
+        parse_lines(&Input::new(text)).unwrap()
+    }
+
+    #[test]
+    fn test_join_keeps_leading_zeros() {
+        // 705 and 1000, not 75 and 1000
+        let parsed = races("Time: 7 05\nDistance: 10 00");
+        assert_eq!(solve_part2(&parsed), Ok(n_wins(705, 1000)));
+    }
+
+    #[test]
+    fn test_n_wins() {
+        // The example races, the last one with ties at both ends
+        assert_eq!(n_wins(7, 9), 4);
+        assert_eq!(n_wins(15, 40), 8);
+        assert_eq!(n_wins(30, 200), 9);
+        assert_eq!(n_wins(71530, 940200), 71503);
+        // Can't win at all, or only by holding exactly half the time
+        assert_eq!(n_wins(2, 1), 0);
+        assert_eq!(n_wins(7, 100), 0);
+        assert_eq!(n_wins(4, 3), 1);
+    }
+
     #[test]
-    fn test_d6_p1() {
-        let s = r#"Time:      7  15   30
-Distance:  9  40  200
-        "#;
-        let inputs = s.split('\n').map(|s| s.to_string()).collect();
-        assert_eq!(solve_part1(inputs), 288);
+    fn test_mismatched_races() {
+        let err = parse_lines(&Input::new("Time: 2 7\nDistance: 1")).unwrap_err();
+        assert_eq!(err.message, "2 times but 1 distances");
+        assert_eq!(err.line, Some(2));
     }
 
     #[test]
-    fn test_d6_p2() {
-        let s = r#"Time:      7  15   30
-Distance:  9  40  200
-        "#;
-        let inputs = s.split('\n').map(|s| s.to_string()).collect();
-        assert_eq!(solve_part2(inputs), 71503);
+    fn test_no_races() {
+        let err = solve_part2(&races("Time:\nDistance:")).unwrap_err();
+        assert_eq!(err.message, "No time to join");
     }
 }
@@ -1,8 +1,9 @@
+use aoc_runner::{Answer, Error, Input, Solution};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
-enum Card {
+pub enum Card {
     Joker = 0,
     Two = 2,
     Three = 3,
@@ -20,28 +21,22 @@ enum Card {
 }
 
 impl Card {
-    fn from_char(c: char, j_is_joker: bool) -> Self {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            '2' => Card::Two,
-            '3' => Card::Three,
-            '4' => Card::Four,
-            '5' => Card::Five,
-            '6' => Card::Six,
-            '7' => Card::Seven,
-            '8' => Card::Eight,
-            '9' => Card::Nine,
-            'T' => Card::T,
-            'J' => {
-                if j_is_joker {
-                    Card::Joker
-                } else {
-                    Card::J
-                }
-            }
-            'Q' => Card::Q,
-            'K' => Card::K,
-            'A' => Card::A,
-            _ => panic!("Invalid card: '{}'", c),
+            '2' => Some(Card::Two),
+            '3' => Some(Card::Three),
+            '4' => Some(Card::Four),
+            '5' => Some(Card::Five),
+            '6' => Some(Card::Six),
+            '7' => Some(Card::Seven),
+            '8' => Some(Card::Eight),
+            '9' => Some(Card::Nine),
+            'T' => Some(Card::T),
+            'J' => Some(Card::J),
+            'Q' => Some(Card::Q),
+            'K' => Some(Card::K),
+            'A' => Some(Card::A),
+            _ => None,
         }
     }
 }
@@ -75,7 +70,7 @@ impl From<[Card; 5]> for HandType {
 
         // sort in descending order of count
         let mut counts_vec: Vec<(Card, u64)> = counts.into_iter().collect();
-        counts_vec.sort_by(|a, b| b.1.cmp(&a.1));
+        counts_vec.sort_by_key(|c| std::cmp::Reverse(c.1));
         counts_vec[0].1 += joker_count;
         match counts_vec.len() {
             1 => HandType::FiveOfAKind,
@@ -100,7 +95,7 @@ impl From<[Card; 5]> for HandType {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, PartialOrd)]
+#[derive(Debug, Eq, PartialEq)]
 struct Hand {
     // Card: an array of length 5 of cards
     cards: [Card; 5],
@@ -108,11 +103,7 @@ struct Hand {
 }
 
 impl Hand {
-    fn from_str(s: &str, j_is_joker: bool) -> Self {
-        let mut cards: [Card; 5] = [Card::Two; 5];
-        for (i, c) in s.chars().enumerate() {
-            cards[i] = Card::from_char(c, j_is_joker);
-        }
+    fn new(cards: [Card; 5]) -> Self {
         Hand {
             cards,
             hand_type: cards.into(),
@@ -130,19 +121,50 @@ impl Ord for Hand {
     }
 }
 
-fn parse_inputs(inputs: Vec<String>, j_is_joker: bool) -> Vec<(Hand, u64)> {
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn parse_line(line: &str) -> Result<([Card; 5], u64), Error> {
+    let (hand, bid) = line
+        .split_once(' ')
+        .ok_or_else(|| Error::at(line, line, "Expected '<hand> <bid>'"))?;
+    let mut cards: [Card; 5] = [Card::Two; 5];
+    if hand.chars().count() != cards.len() {
+        return Err(Error::at(line, hand, "Expected 5 cards"));
+    }
+    for (card, (i, c)) in cards.iter_mut().zip(hand.char_indices()) {
+        *card = Card::from_char(c)
+            .ok_or_else(|| Error::at(line, &hand[i..], format!("Invalid card: '{}'", c)))?;
+    }
+    let bid = bid
+        .parse::<u64>()
+        .map_err(|_| Error::at(line, bid, "Invalid bid"))?;
+    Ok((cards, bid))
+}
+
+fn parse_inputs(input: &Input) -> Result<Vec<([Card; 5], u64)>, Error> {
+    aoc_runner::parse_lines(input.lines(), parse_line)
+}
+
+// Whether J is a jack or a joker changes the hand type, so hands are built per part
+fn to_hands(inputs: &[([Card; 5], u64)], j_is_joker: bool) -> Vec<(Hand, u64)> {
     inputs
         .iter()
-        .map(|s| {
-            let hand = Hand::from_str(&s[..5], j_is_joker);
-            let bid = s[6..].parse::<u64>().unwrap();
-            (hand, bid)
+        .map(|(cards, bid)| {
+            let cards = cards.map(|c| match c {
+                Card::J if j_is_joker => Card::Joker,
+                c => c,
+            });
+            (Hand::new(cards), *bid)
         })
         .collect()
 }
 
-pub fn solve_part1(inputs: Vec<String>) -> u64 {
-    let mut hands = parse_inputs(inputs, false);
+pub fn solve_part1(inputs: &[([Card; 5], u64)]) -> u64 {
+    let mut hands = to_hands(inputs, false);
     hands.sort_by(|a, b| a.0.cmp(&b.0));
     let total_winnings: u64 = hands
         .iter()
@@ -152,8 +174,8 @@ pub fn solve_part1(inputs: Vec<String>) -> u64 {
     total_winnings
 }
 
-pub fn solve_part2(inputs: Vec<String>) -> u64 {
-    let mut hands = parse_inputs(inputs, true);
+pub fn solve_part2(inputs: &[([Card; 5], u64)]) -> u64 {
+    let mut hands = to_hands(inputs, true);
     hands.sort_by(|a, b| a.0.cmp(&b.0));
     let total_winnings: u64 = hands
         .iter()
@@ -163,29 +185,20 @@ pub fn solve_part2(inputs: Vec<String>) -> u64 {
     total_winnings
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_d7_p1() {
-        let s = r#"32T3K 765
-        T55J5 684
-        KK677 28
-        KTJJT 220
-        QQQJA 483"#;
-        let inputs = s.split('\n').map(|s| s.trim().to_string()).collect();
-        assert_eq!(solve_part1(inputs), 6440);
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed = Vec<([Card; 5], u64)>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, Error> {
+        parse_inputs(input)
     }
 
-    #[test]
-    fn test_d7_p2() {
-        let s = r#"32T3K 765
-        T55J5 684
-        KK677 28
-        KTJJT 220
-        QQQJA 483"#;
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error> {
+        Ok(solve_part1(parsed).into())
+    }
 
-        let inputs = s.split('\n').map(|s| s.trim().to_string()).collect();
-        assert_eq!(solve_part2(inputs), 5905);
+    fn part2(parsed: &Self::Parsed) -> Option<Result<Answer, Error>> {
+        Some(Ok(solve_part2(parsed).into()))
     }
 }
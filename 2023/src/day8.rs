@@ -1,24 +1,10 @@
+use aoc_runner::{Answer, Error, Input, Solution, Turn};
 use nom::{
     bytes::complete::tag, character::complete::alphanumeric1, character::complete::multispace0, IResult,
 };
 use std::collections::HashMap;
 
-#[derive(Debug)]
-enum Direction {
-    Left = 0,
-    Right = 1,
-}
-impl From<char> for Direction {
-    fn from(c: char) -> Self {
-        match c {
-            'L' => Self::Left,
-            'R' => Self::Right,
-            _ => panic!("Invalid direction"),
-        }
-    }
-}
-
-fn parse_line(line: &str) -> IResult<&str, (String, (String, String))> {
+fn parse_line(line: &str) -> IResult<&str, (&str, (&str, &str))> {
     let (i, key) = alphanumeric1(line)?;
     let (i, _) = multispace0(i)?;
     let (i, _) = tag("=")(i)?;
@@ -28,110 +14,230 @@ fn parse_line(line: &str) -> IResult<&str, (String, (String, String))> {
     let (i, _) = tag(",")(i)?;
     let (i, _) = multispace0(i)?;
     let (i, right) = alphanumeric1(i)?;
-    Ok((i, (key.to_string(), (left.to_string(), right.to_string()))))
+    Ok((i, (key, (left, right))))
 }
 
-pub fn solve_part1(inputs: Vec<String>) -> u64 {
-    let directions: Vec<Direction> = inputs[0].chars().map(|c| c.into()).collect();
+/// The network with node names interned to dense ids at parse time, so a
+/// step is an array lookup.
+#[derive(Debug)]
+pub struct Network {
+    turns: Vec<Turn>,
+    /// Name of each id.
+    names: Vec<String>,
+    /// `next[id]` is where turning left and right lead.
+    next: Vec<[u32; 2]>,
+}
 
-    let map: HashMap<String, (String, String)> = inputs[2..]
-        .iter()
-        .map(|l| parse_line(l).unwrap().1)
-        .collect();
-
-    let mut loc: String = "AAA".to_string();
-    let dest: String = "ZZZ".to_string();
-    for (i, direction) in directions.iter().cycle().enumerate() {
-        if loc == dest {
-            return i as u64;
+impl Network {
+    fn id(&self, name: &str) -> Result<u32, Error> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|id| id as u32)
+            .ok_or_else(|| Error::new(format!("Unknown node: {}", name)))
+    }
+
+    fn step(&self, id: u32, turn: Turn) -> u32 {
+        let [left, right] = self.next[id as usize];
+        match turn {
+            Turn::Left => left,
+            Turn::Right => right,
         }
-        loc = match direction {
-            Direction::Left => map.get(&loc).unwrap().0.to_string(),
-            Direction::Right => map.get(&loc).unwrap().1.to_string(),
+    }
+
+    /// Steps from `start`, following the turns from the `from`th on, to the
+    /// first node `is_end` accepts, and that node.
+    fn steps(
+        &self,
+        start: u32,
+        from: u64,
+        is_end: impl Fn(u32) -> bool,
+    ) -> Result<(u64, u32), Error> {
+        // Past every (node, turn) pair the walk can only repeat itself
+        let limit = self.names.len() * self.turns.len();
+        let from = (from % self.turns.len() as u64) as usize;
+        let turns = self.turns.iter().cycle().skip(from);
+        let mut id = start;
+        for (i, &turn) in turns.take(limit + 1).enumerate() {
+            if is_end(id) {
+                return Ok((i as u64, id));
+            }
+            id = self.step(id, turn);
+        }
+        Err(Error::new(format!(
+            "{} never reaches the end",
+            self.names[start as usize]
+        )))
+    }
+}
+
+fn parse_network(input: &Input) -> Result<Network, Error> {
+    let mut paragraphs = input.paragraphs();
+    let first = paragraphs
+        .next()
+        .ok_or_else(|| Error::new("Missing directions"))?;
+    let turns = first
+        .char_indices()
+        .map(|(i, c)| {
+            Turn::try_from(c).map_err(|e| Error::at(first, &first[i..], e).on_line(1, first))
+        })
+        .collect::<Result<Vec<Turn>, Error>>()?;
+
+    let mut ids: HashMap<&str, u32> = HashMap::new();
+    let mut names = Vec::new();
+    // The line that mentions each id first, to point unknown nodes out
+    let mut mentions = Vec::new();
+    let mut next: Vec<Option<[u32; 2]>> = Vec::new();
+    let nodes = paragraphs.next().unwrap_or("");
+    for l in nodes.lines() {
+        let mut intern = |name| {
+            *ids.entry(name).or_insert_with(|| {
+                names.push(name);
+                mentions.push(l);
+                next.push(None);
+                names.len() as u32 - 1
+            })
+        };
+        let with_line = |err: Error| match input.line_number(l) {
+            Some(line) => err.on_line(line, l),
+            None => err,
         };
+        let (_, (name, (left, right))) =
+            parse_line(l).map_err(|e| with_line(Error::from_nom(l, e)))?;
+        let (key, left, right) = (intern(name), intern(left), intern(right));
+        if next[key as usize].replace([left, right]).is_some() {
+            return Err(with_line(Error::at(l, name, "Duplicate node")));
+        }
     }
-    return 0;
+    let next = next
+        .into_iter()
+        .enumerate()
+        .map(|(id, next)| {
+            next.ok_or_else(|| {
+                let l = mentions[id];
+                let err = Error::new(format!("Unknown node: {}", names[id]));
+                match input.line_number(l) {
+                    Some(line) => err.on_line(line, l),
+                    None => err,
+                }
+            })
+        })
+        .collect::<Result<Vec<[u32; 2]>, Error>>()?;
+    Ok(Network {
+        turns,
+        names: names.into_iter().map(str::to_string).collect(),
+        next,
+    })
 }
 
-pub fn solve_part2(inputs: Vec<String>) -> u64 {
-    let directions: Vec<Direction> = inputs[0].chars().map(|c| c.into()).collect();
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
 
-    let map: HashMap<String, (String, String)> = inputs[2..]
-        .iter()
-        .map(|l| parse_line(l).unwrap().1)
-        .collect();
+fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
 
-    let mut walkers: Vec<String> = map.keys().filter(|k| k.ends_with('A')).cloned().collect();
-    println!("Walkers: {:?}", walkers);
+pub fn solve_part1(network: &Network) -> Result<u64, Error> {
+    let end = network.id("ZZZ")?;
+    Ok(network.steps(network.id("AAA")?, 0, |id| id == end)?.0)
+}
 
-    for (i, direction) in directions.iter().cycle().enumerate() {
-        // Check if all walkers are equal to dest
-        if walkers.iter().all(|w| w.ends_with('Z')) {
-            return i as u64;
-        }
-        // Update walkers
-        for w in walkers.iter_mut() {
-            let new_loc = match direction {
-                Direction::Left => map.get(w).unwrap().0.to_string(),
-                Direction::Right => map.get(w).unwrap().1.to_string(),
-            };
-            // Check if walkers are stuck
-            if new_loc == *w {
-                println!("Stuck at {}", w);
-                return i as u64;
+/// Assumes every ghost's walk from its `A` node loops back to its first `Z`
+/// node every as many steps again, so they all meet at the least common
+/// multiple. The loop is followed until it is back at the same point of the
+/// turns, where it repeats for good; an input that breaks the assumption is
+/// an error rather than a wrong answer.
+pub fn solve_part2(network: &Network) -> Result<u64, Error> {
+    let is_end: Vec<bool> = network.names.iter().map(|n| n.ends_with('Z')).collect();
+    let is_end = |id: u32| is_end[id as usize];
+    let turns = network.turns.len() as u64;
+    let mut steps = 1;
+    for (start, name) in network
+        .names
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| n.ends_with('A'))
+    {
+        let (first, end) = network.steps(start as u32, 0, is_end)?;
+        let mut at = first;
+        loop {
+            let turn = network.turns[(at % turns) as usize];
+            let (again, back) = network.steps(network.step(end, turn), at + 1, is_end)?;
+            if back != end || again + 1 != first {
+                return Err(Error::new(format!(
+                    "{} does not loop: {} after {} steps, then {} after {} more",
+                    name,
+                    network.names[end as usize],
+                    at,
+                    network.names[back as usize],
+                    again + 1
+                )));
+            }
+            at += first;
+            if at % turns == first % turns {
+                break;
             }
-            *w = new_loc;
-        }
-        // Print every 100k steps
-        if i % 1000000 == 0 {
-            println!("Step: {}", i);
         }
+        steps = lcm(steps, first);
+    }
+    Ok(steps)
+}
 
+pub struct Day8;
 
+impl Solution for Day8 {
+    type Parsed = Network;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, Error> {
+        parse_network(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error> {
+        solve_part1(parsed).map(Answer::from)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Option<Result<Answer, Error>> {
+        Some(solve_part2(parsed).map(Answer::from))
     }
-    return 0;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn test_unknown_node() {
+        let err = parse_network(&Input::new("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)")).unwrap_err();
+        assert_eq!(err.message, "Unknown node: BBB");
+        assert_eq!(err.line, Some(3));
+    }
+
+    #[test]
+    fn test_duplicate_node() {
+        let err = parse_network(&Input::new("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)")).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(4), Some(1)));
+    }
+
     #[test]
-    fn test_d8_p1() {
-        let s = r#"RL
-
-        AAA = (BBB, CCC)
-        BBB = (DDD, EEE)
-        CCC = (ZZZ, GGG)
-        DDD = (DDD, DDD)
-        EEE = (EEE, EEE)
-        GGG = (GGG, GGG)
-        ZZZ = (ZZZ, ZZZ)"#;
-        let inputs = s.split('\n').map(|s| s.trim().to_string()).collect();
-        assert_eq!(solve_part1(inputs), 2);
-
-        let s = r#"LLR
-
-        AAA = (BBB, BBB)
-        BBB = (AAA, ZZZ)
-        ZZZ = (ZZZ, ZZZ)"#;
-        let inputs = s.split('\n').map(|s| s.trim().to_string()).collect();
-        assert_eq!(solve_part1(inputs), 6);
+    fn test_never_reaches_end() {
+        let network = parse_network(&Input::new("L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)")).unwrap();
+        assert!(solve_part1(&network).is_err());
     }
 
     #[test]
-    fn test_d8_p2() {
-        let s = r#"LR
-
-        11A = (11B, XXX)
-        11B = (XXX, 11Z)
-        11Z = (11B, XXX)
-        22A = (22B, XXX)
-        22B = (22C, 22C)
-        22C = (22Z, 22Z)
-        22Z = (22B, 22B)
-        XXX = (XXX, XXX)"#;
-
-        let inputs = s.split('\n').map(|s| s.trim().to_string()).collect();
-        assert_eq!(solve_part2(inputs), 6);
+    fn test_ghosts_must_loop() {
+        let text = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n\
+                    22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n\
+                    22Z = (22B, 22B)\nXXX = (XXX, XXX)";
+        assert_eq!(solve_part2(&parse_network(&Input::new(text)).unwrap()), Ok(6));
+        // 11Z now leads on to another Z node, so 11A's steps don't repeat
+        let text = text.replace("11Z = (11B, XXX)", "11Z = (33Z, XXX)\n33Z = (XXX, XXX)");
+        let err = solve_part2(&parse_network(&Input::new(&text)).unwrap()).unwrap_err();
+        assert_eq!(err.message, "11A does not loop: 11Z after 2 steps, then 33Z after 1 more");
     }
 }
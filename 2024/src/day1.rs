@@ -1,3 +1,4 @@
+use aoc_runner::{Answer, Error, Input, Solution};
 use nom::{
     character::complete::{i32 as nom_i32, multispace1},
     sequence::separated_pair,
@@ -10,52 +11,51 @@ fn parse_i32_pair(input: &str) -> IResult<&str, (i32, i32)> {
     separated_pair(nom_i32, multispace1, nom_i32)(input)
 }
 
-pub fn solve_part1(inputs: Vec<String>) -> u32 {
-    let (mut left, mut right): (Vec<i32>, Vec<i32>) =
-        inputs.iter().map(|s| parse_i32_pair(s).unwrap().1).unzip();
+type Lists = (Vec<i32>, Vec<i32>);
+
+pub fn solve_part1((left, right): &Lists) -> i32 {
+    let mut left = left.clone();
+    let mut right = right.clone();
     left.sort();
     right.sort();
-    zip(left, right).map(|(l, r)| (r - l).abs()).sum::<i32>() as u32
+    zip(left, right).map(|(l, r)| (r - l).abs()).sum()
 }
 
-fn value_counts(v: Vec<i32>) -> HashMap<i32, i32> {
+fn value_counts(v: &[i32]) -> HashMap<i32, i32> {
     let mut counts = HashMap::new();
-    for i in v {
+    for &i in v {
         let count = counts.entry(i).or_insert(0);
         *count += 1;
     }
     counts
 }
 
-pub fn solve_part2(inputs: Vec<String>) -> u32 {
-    let (left, right): (Vec<i32>, Vec<i32>) =
-        inputs.iter().map(|s| parse_i32_pair(s).unwrap().1).unzip();
-
+pub fn solve_part2((left, right): &Lists) -> i32 {
     let right_counts = value_counts(right);
     left.iter()
         .map(|l| l * right_counts.get(l).unwrap_or(&0))
-        .sum::<i32>() as u32
+        .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_1() {
-        let inputs: Vec<String> = Vec::from(["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"])
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        assert_eq!(solve_part1(inputs), 11);
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = Lists;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, Error> {
+        let pairs = aoc_runner::parse_lines(input.lines(), |s| {
+            parse_i32_pair(s)
+                .map(|(_, pair)| pair)
+                .map_err(|e| Error::from_nom(s, e))
+        })?;
+        Ok(pairs.into_iter().unzip())
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error> {
+        Ok(solve_part1(parsed).into())
     }
 
-    #[test]
-    fn test_part_2() {
-        let inputs: Vec<String> = Vec::from(["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"])
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        assert_eq!(solve_part2(inputs), 31);
+    fn part2(parsed: &Self::Parsed) -> Option<Result<Answer, Error>> {
+        Some(Ok(solve_part2(parsed).into()))
     }
 }
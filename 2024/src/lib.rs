@@ -0,0 +1,10 @@
+pub const YEAR: aoc_runner::Year = aoc_runner::Year {
+    year: 2024,
+    solutions,
+    data_dir: concat!(env!("CARGO_MANIFEST_DIR"), "/data"),
+    answers: include_str!("../answers.txt"),
+    examples: EXAMPLES,
+    example_answers: EXAMPLE_ANSWERS,
+};
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
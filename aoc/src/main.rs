@@ -0,0 +1,5 @@
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    aoc_runner::main(&[aoc2023::YEAR, aoc2024::YEAR])
+}
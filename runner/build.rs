@@ -0,0 +1,19 @@
+use std::env;
+use std::process::Command;
+
+// Records the compiler version for the benchmark history.
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|o| {
+            // "rustc 1.80.0 (051478957 2024-07-21)"
+            let out = String::from_utf8_lossy(&o.stdout).into_owned();
+            out.split_whitespace().nth(1).map(str::to_string)
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=AOC_RUSTC_VERSION={}", version);
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
@@ -0,0 +1,150 @@
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// A puzzle answer, kept in the type the day computed it in so nothing is
+/// truncated on the way to the results table.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    String(String),
+}
+
+impl Answer {
+    // (is_negative, magnitude) so integers of any width compare by value
+    fn integer(&self) -> Option<(bool, u128)> {
+        match *self {
+            Answer::I64(v) => Some((v < 0, v.unsigned_abs() as u128)),
+            Answer::U64(v) => Some((false, v as u128)),
+            Answer::I128(v) => Some((v < 0, v.unsigned_abs())),
+            Answer::U128(v) => Some((false, v)),
+            Answer::String(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::String(a), Answer::String(b)) => a == b,
+            _ => match (self.integer(), other.integer()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if let (Answer::String(a), Answer::String(b)) = (self, other) {
+            return Some(a.cmp(b));
+        }
+        let ((a_neg, a), (b_neg, b)) = (self.integer()?, other.integer()?);
+        Some(match (a_neg, b_neg) {
+            (false, false) => a.cmp(&b),
+            (true, true) => b.cmp(&a),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+        })
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::I64(v) => v.fmt(f),
+            Answer::U64(v) => v.fmt(f),
+            Answer::I128(v) => v.fmt(f),
+            Answer::U128(v) => v.fmt(f),
+            Answer::String(v) => f.pad(v),
+        }
+    }
+}
+
+// Picks the narrowest integer variant that holds the value, else a string
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(if let Ok(v) = s.parse::<u64>() {
+            Answer::U64(v)
+        } else if let Ok(v) = s.parse::<i64>() {
+            Answer::I64(v)
+        } else if let Ok(v) = s.parse::<u128>() {
+            Answer::U128(v)
+        } else if let Ok(v) = s.parse::<i128>() {
+            Answer::I128(v)
+        } else {
+            Answer::String(s.to_string())
+        })
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident: $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    Answer::$variant(v.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(I64: i8, i16, i32, i64);
+impl_from!(U64: u8, u16, u32, u64);
+impl_from!(I128: i128);
+impl_from!(U128: u128);
+impl_from!(String: String, &str);
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Answer::U64(v as u64)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(v: isize) -> Self {
+        Answer::I64(v as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_is_lossless() {
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(i128::MIN).to_string(), i128::MIN.to_string());
+        assert_eq!(format!("{:^7}", Answer::from("abc")), "  abc  ");
+    }
+
+    #[test]
+    fn test_compare_across_widths() {
+        assert_eq!(Answer::from(42u32), Answer::from(42i128));
+        assert_ne!(Answer::from(-1i64), Answer::from(u128::MAX));
+        assert_ne!(Answer::from(1u64), Answer::from("1"));
+        assert!(Answer::from(-5i64) < Answer::from(3u64));
+        assert!(Answer::from(u64::MAX) < Answer::from(u64::MAX as u128 + 1));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("42".parse(), Ok(Answer::U64(42)));
+        assert_eq!("-42".parse(), Ok(Answer::I64(-42)));
+        assert_eq!(
+            "340282366920938463463374607431768211455".parse(),
+            Ok(Answer::U128(u128::MAX))
+        );
+        assert_eq!("HELLO".parse(), Ok(Answer::from("HELLO")));
+    }
+}
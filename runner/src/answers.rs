@@ -0,0 +1,107 @@
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::solution::Part;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// Known answers for a year, read from its `answers.txt`: one
+/// `<day> <part> <answer>` per line, `#` starts a comment.
+///
+/// Examples use the same format keyed by example name instead of day.
+#[derive(Debug, Default)]
+pub struct Answers<K = u32> {
+    answers: HashMap<(K, u32), Answer>,
+}
+
+impl<K: FromStr + Eq + Hash> Answers<K> {
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut answers = HashMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let content = line.split('#').next().unwrap_or("").trim();
+            if content.is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = content.split_whitespace().collect();
+            let [day, part, answer] = fields[..] else {
+                return Err(Error::at(line, content, "Expected '<day> <part> <answer>'")
+                    .on_line(i + 1, line));
+            };
+            let day: K = day
+                .parse()
+                .map_err(|_| Error::at(line, day, "Invalid day").on_line(i + 1, line))?;
+            let part: u32 = match part.parse() {
+                Ok(p @ 1..=2) => p,
+                _ => return Err(Error::at(line, part, "Invalid part").on_line(i + 1, line)),
+            };
+            let Ok(answer) = answer.parse::<Answer>();
+            answers.insert((day, part), answer);
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u32, &Answer)> {
+        self.answers.iter().map(|((k, part), a)| (k, *part, a))
+    }
+
+    pub fn get(&self, day: K, part: Part) -> Option<&Answer> {
+        self.answers.get(&(day, part as u32))
+    }
+
+    pub fn status(&self, day: K, part: Part, actual: Option<&Answer>) -> Status {
+        match (self.get(day, part), actual) {
+            (None, _) => Status::Unknown,
+            (Some(expected), Some(actual)) if expected == actual => Status::Correct,
+            (Some(expected), _) => Status::Wrong(expected.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Correct,
+    Wrong(Answer),
+    Unknown,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Status::Correct => "correct",
+            Status::Wrong(_) => "wrong",
+            Status::Unknown => "unknown",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_status() {
+        let answers =
+            Answers::<u32>::parse("# day part answer\n1 1 142\n1 2 281 # example\n\n").unwrap();
+        assert_eq!(
+            answers.status(1, Part::Part1, Some(&Answer::from(142u32))),
+            Status::Correct
+        );
+        assert_eq!(
+            answers.status(1, Part::Part2, Some(&Answer::from(280u32))),
+            Status::Wrong(Answer::from(281u32))
+        );
+        assert_eq!(
+            answers.status(1, Part::Part2, None),
+            Status::Wrong(Answer::from(281u32))
+        );
+        assert_eq!(answers.status(2, Part::Part1, None), Status::Unknown);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Answers::<u32>::parse("1 1 142\n1 3 7").unwrap_err();
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.column, Some(3));
+    }
+}
@@ -0,0 +1,256 @@
+use microbench::{retain, statistics::Model, time::Nanoseconds, Sample};
+use std::time::{Duration, Instant};
+
+/// How long and how often `--bench` runs each part.
+#[derive(clap::Args, Debug, Clone)]
+pub struct BenchOptions {
+    /// Measure each part repeatedly instead of solving it once
+    #[clap(long)]
+    pub bench: bool,
+    /// Seconds to spend measuring each part
+    #[clap(long, value_name = "SECS", default_value_t = 1.0, requires = "bench", value_parser = seconds)]
+    pub bench_time: f64,
+    /// Seconds to run each part before measuring it
+    #[clap(long, value_name = "SECS", default_value_t = 0.1, requires = "bench", value_parser = seconds)]
+    pub warmup: f64,
+    /// Keep measuring past --bench-time until a part ran this many times
+    #[clap(long, value_name = "N", default_value_t = 1, requires = "bench")]
+    pub min_iterations: u64,
+    /// Stop measuring once a part ran this many times
+    #[clap(long, value_name = "N", requires = "bench")]
+    pub max_iterations: Option<u64>,
+    /// Stop measuring after this many samples
+    #[clap(long, value_name = "N", requires = "bench")]
+    pub samples: Option<usize>,
+}
+
+/// A number of seconds that fits a `Duration`.
+fn seconds(arg: &str) -> Result<f64, String> {
+    let secs: f64 = arg
+        .parse()
+        .map_err(|_| format!("{} is not a number", arg))?;
+    match Duration::try_from_secs_f64(secs) {
+        Ok(_) => Ok(secs),
+        Err(_) => Err(format!("{} is not 0 or more seconds", arg)),
+    }
+}
+
+/// Each sample runs the function this many times more than the last one.
+const FACTOR: f64 = 1.1;
+
+/// Timing of one part: the slope of elapsed time over iterations.
+#[derive(Debug, Clone, Copy)]
+pub struct Timing {
+    pub iterations: u64,
+    pub per_iteration: Duration,
+    /// Goodness of fit, `None` when there were too few samples for a model.
+    pub r2: Option<f64>,
+    /// Standard error of `per_iteration` from the same model.
+    pub std_error: Option<Duration>,
+}
+
+/// Runs `f` once and times it.
+pub fn once<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// CPU time the calling thread has used so far, `None` where there is no
+/// per-thread clock.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn thread_cpu_time() -> Option<Duration> {
+    use std::ffi::{c_int, c_long};
+
+    #[repr(C)]
+    struct Timespec {
+        tv_sec: c_long,
+        tv_nsec: c_long,
+    }
+    #[cfg(target_os = "linux")]
+    const CLOCK_THREAD_CPUTIME_ID: c_int = 3;
+    #[cfg(target_os = "macos")]
+    const CLOCK_THREAD_CPUTIME_ID: c_int = 16;
+    extern "C" {
+        fn clock_gettime(clock: c_int, time: *mut Timespec) -> c_int;
+    }
+
+    let mut time = Timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: `time` is a valid timespec for clock_gettime to fill in
+    match unsafe { clock_gettime(CLOCK_THREAD_CPUTIME_ID, &mut time) } {
+        0 => Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32)),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+/// Warms up, then collects samples of a growing number of runs of `f`
+/// within the limits of `options`.
+pub fn measure<T>(options: &BenchOptions, mut f: impl FnMut() -> T) -> Timing {
+    let warmup = Duration::from_secs_f64(options.warmup);
+    let start = Instant::now();
+    while start.elapsed() < warmup {
+        retain(f());
+    }
+
+    let time = Duration::from_secs_f64(options.bench_time);
+    let max_iterations = options.max_iterations.unwrap_or(u64::MAX);
+    let mut samples: Vec<Sample> = Vec::new();
+    let mut total = 0u64;
+    let mut size = 1.0f64;
+    let start = Instant::now();
+    loop {
+        let done = start.elapsed() >= time && total >= options.min_iterations;
+        if done || total >= max_iterations || options.samples.is_some_and(|n| samples.len() >= n) {
+            break;
+        }
+        let iterations = (size as u64).min(max_iterations - total);
+        let sample_start = Instant::now();
+        for _ in 0..iterations {
+            retain(f());
+        }
+        samples.push(Sample {
+            iterations,
+            elapsed: Nanoseconds(sample_start.elapsed().as_nanos() as u64),
+        });
+        total += iterations;
+        size = (size * FACTOR).ceil();
+    }
+    analyze(&samples)
+}
+
+fn analyze(samples: &[Sample]) -> Timing {
+    let iterations = samples.iter().map(|s| s.iterations).sum();
+    let elapsed: u64 = samples.iter().map(|s| s.elapsed.0).sum();
+    let mean = Timing {
+        iterations,
+        per_iteration: Duration::from_nanos(elapsed / iterations.max(1)),
+        r2: None,
+        std_error: None,
+    };
+    // A line needs at least two distinct iteration counts
+    if samples.len() < 3 {
+        return mean;
+    }
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|m| (m.iterations as f64, m.elapsed.0 as f64))
+        .collect();
+    let Model { alpha, beta, r2 } = points.iter().copied().collect::<Model>();
+    if beta < 0.0 || !beta.is_finite() {
+        return mean;
+    }
+    // se(β) = sqrt(Σ residual² / (n - 2) / Σ (x - x̄)²)
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let residuals: f64 = points
+        .iter()
+        .map(|(x, y)| (y - alpha - beta * x).powi(2))
+        .sum();
+    let spread: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let std_error = (residuals / (n - 2.0) / spread).sqrt();
+    Timing {
+        per_iteration: Duration::from_secs_f64(beta / 1e9),
+        r2: Some(r2),
+        std_error: std_error
+            .is_finite()
+            .then(|| Duration::from_secs_f64(std_error / 1e9)),
+        ..mean
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> BenchOptions {
+        BenchOptions {
+            bench: true,
+            bench_time: 10.0,
+            warmup: 0.0,
+            min_iterations: 1,
+            max_iterations: None,
+            samples: None,
+        }
+    }
+
+    #[test]
+    fn test_max_iterations() {
+        let mut calls = 0;
+        let options = BenchOptions {
+            max_iterations: Some(50),
+            ..options()
+        };
+        let timing = measure(&options, || calls += 1);
+        assert_eq!(timing.iterations, 50);
+        assert_eq!(calls, 50);
+    }
+
+    #[test]
+    fn test_samples() {
+        let options = BenchOptions {
+            samples: Some(4),
+            ..options()
+        };
+        // 1 + 2 + 3 + 4 runs
+        assert_eq!(measure(&options, || ()).iterations, 10);
+    }
+
+    #[test]
+    fn test_analyze_linear() {
+        let samples: Vec<Sample> = (1..=5)
+            .map(|i| Sample {
+                iterations: i,
+                elapsed: Nanoseconds(100 * i + 7),
+            })
+            .collect();
+        let timing = analyze(&samples);
+        assert_eq!(timing.iterations, 15);
+        assert_eq!(timing.per_iteration, Duration::from_nanos(100));
+        assert_eq!(timing.std_error, Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_seconds() {
+        assert_eq!(seconds("0.5"), Ok(0.5));
+        assert_eq!(seconds("0"), Ok(0.0));
+        for bad in ["-1", "nan", "inf", "1e30", "x"] {
+            assert!(seconds(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_thread_cpu_time() {
+        let Some(start) = thread_cpu_time() else {
+            return;
+        };
+        let busy = Instant::now();
+        let mut n = 0u64;
+        while busy.elapsed() < Duration::from_millis(20) {
+            n = std::hint::black_box(n + 1);
+        }
+        let used = thread_cpu_time().unwrap() - start;
+        assert!(used >= Duration::from_millis(10), "{:?}", used);
+        // Sleeping costs no CPU time
+        let start = thread_cpu_time().unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(thread_cpu_time().unwrap() - start < Duration::from_millis(25));
+    }
+
+    #[test]
+    fn test_min_iterations() {
+        let options = BenchOptions {
+            bench_time: 0.0,
+            min_iterations: 20,
+            ..options()
+        };
+        assert!(measure(&options, || ()).iterations >= 20);
+    }
+}
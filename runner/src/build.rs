@@ -0,0 +1,112 @@
+// Build script helper shared by the year crates.
+use crate::answers::Answers;
+use crate::examples::example_day;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Generates the day registry for the calling crate: every `src/dayN.rs`
+/// becomes a module and its `DayN` solution is registered, so adding a day
+/// never touches `lib.rs`. Include the result with
+/// `include!(concat!(env!("OUT_DIR"), "/days.rs"))`.
+///
+/// Files in `examples/` are embedded as `EXAMPLES`, and a test is generated
+/// for every example and part listed in `examples/answers.txt`.
+pub fn register_days() {
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let src = Path::new(&root).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let mut days: Vec<u32> = fs::read_dir(&src)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix("day")?.strip_suffix(".rs")?.parse().ok()
+        })
+        .collect();
+    days.sort();
+
+    let mut out = String::new();
+    for day in days.iter() {
+        let path = src.join(format!("day{}.rs", day));
+        writeln!(out, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(out, "mod day{};", day).unwrap();
+    }
+    writeln!(
+        out,
+        "pub fn solutions() -> Vec<Box<dyn aoc_runner::Solver>> {{"
+    )
+    .unwrap();
+    writeln!(out, "    vec![").unwrap();
+    for day in days.iter() {
+        writeln!(
+            out,
+            "        aoc_runner::register::<day{0}::Day{0}>({0}),",
+            day
+        )
+        .unwrap();
+    }
+    writeln!(out, "    ]").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    write_examples(&mut out, &Path::new(&root).join("examples"));
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(dest, out).unwrap();
+}
+
+fn write_examples(out: &mut String, dir: &Path) {
+    println!("cargo:rerun-if-changed={}", dir.display());
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let name = name.strip_suffix(".txt")?;
+            example_day(name).map(|_| name.to_string())
+        })
+        .collect();
+    names.sort_by_key(|name| (example_day(name), name.clone()));
+
+    writeln!(out, "pub const EXAMPLES: &[(&str, &str)] = &[").unwrap();
+    for name in names.iter() {
+        let path = dir.join(format!("{}.txt", name));
+        writeln!(out, "    ({:?}, include_str!({:?})),", name, path).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    let answers_path = dir.join("answers.txt");
+    let Ok(text) = fs::read_to_string(&answers_path) else {
+        writeln!(out, "pub const EXAMPLE_ANSWERS: &str = \"\";").unwrap();
+        return;
+    };
+    writeln!(
+        out,
+        "pub const EXAMPLE_ANSWERS: &str = include_str!({:?});",
+        answers_path
+    )
+    .unwrap();
+    let answers = match Answers::<String>::parse(&text) {
+        Ok(answers) => answers,
+        Err(e) => panic!("{}: {}", answers_path.display(), e),
+    };
+    let mut tests: Vec<(&String, u32)> =
+        answers.iter().map(|(name, part, _)| (name, part)).collect();
+    tests.sort_by_key(|&(name, part)| (example_day(name), name, part));
+
+    writeln!(out, "#[cfg(test)]").unwrap();
+    writeln!(out, "mod examples {{").unwrap();
+    for (name, part) in tests {
+        writeln!(out, "    #[test]").unwrap();
+        writeln!(out, "    fn {}_part{}() {{", name.replace('-', "_"), part).unwrap();
+        writeln!(
+            out,
+            "        aoc_runner::test_example(&super::YEAR, {:?}, aoc_runner::Part::Part{});",
+            name, part
+        )
+        .unwrap();
+        writeln!(out, "    }}").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+}
@@ -0,0 +1,218 @@
+use crate::download::setting;
+use crate::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// The passphrase puzzle inputs are encrypted with.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// `dayN.txt` is stored encrypted as `dayN.txt.enc`.
+pub const EXTENSION: &str = "enc";
+
+/// How openssl gets the key, so it never shows up in `ps`.
+const PASS_VAR: &str = "AOC_OPENSSL_PASS";
+
+/// The key from `$AOC_INPUT_KEY`, or else the `input-key` file in
+/// `config_dir()`.
+pub fn key() -> Result<String, Error> {
+    setting(KEY_VAR, "input-key", "input key")
+}
+
+pub fn is_encrypted(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == EXTENSION)
+}
+
+/// `dayN.txt.enc` for `dayN.txt`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Runs `openssl enc` on `input`. AES-256-CBC with a salted PBKDF2 key,
+/// base64 so the encrypted files diff as text.
+fn openssl(decrypt: bool, key: &str, input: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut command = Command::new("openssl");
+    command.args(["enc", "-aes-256-cbc", "-pbkdf2", "-iter", "100000", "-a"]);
+    if decrypt {
+        command.arg("-d");
+    }
+    command.args(["-pass", &format!("env:{}", PASS_VAR)]);
+    let mut child = command
+        .env(PASS_VAR, key)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Error::new(format!("Could not run openssl: {}", e)))?;
+    // Fed from another thread: openssl blocks on a full stdout pipe until
+    // `wait_with_output` starts reading it
+    let mut stdin = child.stdin.take().unwrap();
+    let (output, written) = thread::scope(|scope| {
+        let writer = scope.spawn(move || stdin.write_all(input));
+        let output = child.wait_with_output();
+        (output, writer.join().unwrap())
+    });
+    let output = output.map_err(|e| Error::new(format!("Could not run openssl: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::new(match decrypt {
+            true => format!("wrong {}? {}", KEY_VAR, stderr.lines().next().unwrap_or("")),
+            false => stderr.trim().to_string(),
+        }));
+    }
+    written.map_err(|e| Error::new(format!("Could not run openssl: {}", e)))?;
+    Ok(output.stdout)
+}
+
+pub fn encrypt(plain: &str, key: &str) -> Result<String, Error> {
+    let encrypted = openssl(false, key, plain.as_bytes())?;
+    Ok(String::from_utf8_lossy(&encrypted).into_owned())
+}
+
+pub fn decrypt(encrypted: &str, key: &str) -> Result<String, Error> {
+    let plain = openssl(true, key, encrypted.as_bytes())?;
+    String::from_utf8(plain).map_err(|_| Error::new(format!("wrong {}? Not UTF-8", KEY_VAR)))
+}
+
+/// Reads `path`, which is encrypted with `key`.
+pub fn read_encrypted(path: &Path, key: &str) -> Result<String, Error> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| Error::new(format!("Could not read {}: {}", path.display(), e)))?;
+    decrypt(&text, key).map_err(|e| {
+        Error::new(format!(
+            "Could not decrypt {}: {}",
+            path.display(),
+            e.message
+        ))
+    })
+}
+
+/// Replaces `from` with `to`, holding `contents`. `from` is only removed
+/// once `check` accepts what was written.
+fn replace(
+    from: &Path,
+    to: &Path,
+    contents: &str,
+    check: impl FnOnce(&str) -> Result<(), Error>,
+) -> Result<(), Error> {
+    fs::write(to, contents)
+        .map_err(|e| Error::new(format!("Could not write {}: {}", to.display(), e)))?;
+    let written = fs::read_to_string(to)
+        .map_err(|e| Error::new(format!("Could not read {}: {}", to.display(), e)))?;
+    check(&written)?;
+    fs::remove_file(from)
+        .map_err(|e| Error::new(format!("Could not remove {}: {}", from.display(), e)))
+}
+
+/// `aoc encrypt`: replaces `dayN.txt` with `dayN.txt.enc`.
+pub fn encrypt_file(path: &Path, key: &str) -> Result<PathBuf, Error> {
+    let plain = fs::read_to_string(path)
+        .map_err(|e| Error::new(format!("Could not read {}: {}", path.display(), e)))?;
+    let to = encrypted_path(path);
+    replace(path, &to, &encrypt(&plain, key)?, |written| {
+        match decrypt(written, key) {
+            Ok(decrypted) if decrypted == plain => Ok(()),
+            _ => Err(Error::new(format!("{} does not decrypt", to.display()))),
+        }
+    })?;
+    Ok(to)
+}
+
+/// `aoc decrypt`: replaces `dayN.txt.enc` with `dayN.txt`.
+pub fn decrypt_file(path: &Path, key: &str) -> Result<PathBuf, Error> {
+    let encrypted = fs::read_to_string(path)
+        .map_err(|e| Error::new(format!("Could not read {}: {}", path.display(), e)))?;
+    let plain = decrypt(&encrypted, key).map_err(|e| {
+        Error::new(format!(
+            "Could not decrypt {}: {}",
+            path.display(),
+            e.message
+        ))
+    })?;
+    let to = path.with_extension("");
+    if to.exists() {
+        return Err(Error::new(format!("{} already exists", to.display())));
+    }
+    replace(path, &to, &plain, |written| match written == plain {
+        true => Ok(()),
+        false => Err(Error::new(format!("Could not write {}", to.display()))),
+    })?;
+    Ok(to)
+}
+
+/// The `dayN.txt` inputs in `dir`, or with `encrypted` the `dayN.txt.enc`
+/// ones, by day.
+pub fn inputs(dir: &Path, encrypted: bool) -> Result<Vec<PathBuf>, Error> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| Error::new(format!("Could not read {}: {}", dir.display(), e)))?;
+    let mut inputs: Vec<(u32, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let name = match encrypted {
+                true => name.strip_suffix(".txt.enc")?,
+                false => name.strip_suffix(".txt")?,
+            };
+            let day = name.strip_prefix("day")?.parse().ok()?;
+            Some((day, entry.path()))
+        })
+        .collect();
+    inputs.sort();
+    Ok(inputs.into_iter().map(|(_, path)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::load_file_with;
+    use crate::temp::TempDir;
+
+    #[test]
+    fn test_encrypt_file() {
+        let dir = TempDir::new("crypt");
+        let plain = dir.join("day1.txt");
+        fs::write(&plain, "3   4\n4   3\n").unwrap();
+        fs::write(dir.join("submissions.txt"), "").unwrap();
+        assert_eq!(inputs(&dir, false), Ok(vec![plain.clone()]));
+
+        let encrypted = encrypt_file(&plain, "secret").unwrap();
+        assert_eq!(encrypted, dir.join("day1.txt.enc"));
+        assert!(!plain.exists());
+        let text = fs::read_to_string(&encrypted).unwrap();
+        assert!(!text.contains("3   4"));
+        assert!(decrypt(&text, "wrong").is_err());
+        assert_eq!(inputs(&dir, true), Ok(vec![encrypted.clone()]));
+
+        assert_eq!(decrypt_file(&encrypted, "secret"), Ok(plain.clone()));
+        assert!(!encrypted.exists());
+        assert_eq!(fs::read_to_string(&plain).unwrap(), "3   4\n4   3\n");
+    }
+
+    #[test]
+    fn test_encrypt_large() {
+        // Bigger than a pipe buffer both ways
+        let plain = "1234567 89\n".repeat(30_000);
+        let encrypted = encrypt(&plain, "secret").unwrap();
+        assert_eq!(decrypt(&encrypted, "secret").unwrap(), plain);
+    }
+
+    #[test]
+    fn test_load_encrypted() {
+        let dir = TempDir::new("crypt-load");
+        let key = || Ok("secret".to_string());
+        let path = dir.join("day1.txt");
+        fs::write(encrypted_path(&path), encrypt("1 2\n", "secret").unwrap()).unwrap();
+        // Asked for dayN.txt, the loader falls back to dayN.txt.enc
+        assert_eq!(load_file_with(&path, key).unwrap().as_str(), "1 2");
+        // Also past an empty stub, but not a real plain input
+        fs::write(&path, "").unwrap();
+        assert_eq!(load_file_with(&path, key).unwrap().as_str(), "1 2");
+        fs::write(&path, "3 4\n").unwrap();
+        assert_eq!(load_file_with(&path, key).unwrap().as_str(), "3 4");
+    }
+}
@@ -0,0 +1,333 @@
+use crate::crypt;
+use crate::error::Error;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The session cookie of a logged in adventofcode.com account.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Overrides `DEFAULT_BASE_URL`, e.g. to test against a local server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Who to contact about the requests, an email address or repository URL,
+/// as Advent of Code asks of automated tools.
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+
+pub const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"));
+
+/// Default time between two requests to the server.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+/// `$XDG_CONFIG_HOME/aoc` or `~/.config/aoc`.
+pub fn config_dir() -> Option<PathBuf> {
+    match env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir).join("aoc")),
+        None => env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/aoc")),
+    }
+}
+
+/// A setting from the `var` environment variable, or else the `file` in
+/// `config_dir()`; `what` names it in the error.
+pub fn setting(var: &str, file: &str, what: &str) -> Result<String, Error> {
+    if let Some(value) = env::var(var).ok().filter(|v| !v.trim().is_empty()) {
+        return Ok(value.trim().to_string());
+    }
+    let path = config_dir().map(|dir| dir.join(file));
+    match path.as_ref().and_then(|p| fs::read_to_string(p).ok()) {
+        Some(value) if !value.trim().is_empty() => Ok(value.trim().to_string()),
+        _ => Err(Error::new(format!(
+            "No {}: set {} or write it to {}",
+            what,
+            var,
+            path.map_or(format!("~/.config/aoc/{}", file), |p| p
+                .display()
+                .to_string())
+        ))),
+    }
+}
+
+/// The session token from `$AOC_SESSION`, or else the `session` file in
+/// `config_dir()`.
+pub fn session_token() -> Result<String, Error> {
+    setting(SESSION_VAR, "session", "session token")
+}
+
+/// The contact from `$AOC_CONTACT`, or else the `contact` file in
+/// `config_dir()`.
+pub fn contact() -> Result<String, Error> {
+    setting(CONTACT_VAR, "contact", "contact address")
+}
+
+/// `USER_AGENT` followed by who to contact.
+pub fn user_agent(contact: &str) -> String {
+    format!("{} (+{})", USER_AGENT, contact)
+}
+
+/// `--base-url`, else `$AOC_BASE_URL`, else adventofcode.com.
+pub fn base_url(arg: Option<&str>) -> String {
+    arg.map(str::to_string)
+        .or_else(|| env::var(BASE_URL_VAR).ok().filter(|u| !u.is_empty()))
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Whether `Downloader::download` had to ask the server, with where the
+/// input is.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Downloaded {
+    Cached(PathBuf),
+    Fetched(PathBuf),
+}
+
+/// Fetches puzzle inputs, waiting at least `interval` between requests.
+///
+/// Requests go through the `curl` binary, which handles TLS for us.
+pub struct Downloader {
+    pub base_url: String,
+    pub session: String,
+    pub user_agent: String,
+    /// Inputs are written encrypted with this key.
+    pub key: Option<String>,
+    pub interval: Duration,
+    last: Option<Instant>,
+}
+
+impl Downloader {
+    pub fn new(base_url: &str, session: &str, contact: &str) -> Self {
+        Downloader {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            user_agent: user_agent(contact),
+            key: None,
+            interval: MIN_INTERVAL,
+            last: None,
+        }
+    }
+
+    fn wait(&mut self) {
+        if let Some(wait) = self
+            .last
+            .and_then(|last| self.interval.checked_sub(last.elapsed()))
+        {
+            thread::sleep(wait);
+        }
+        self.last = Some(Instant::now());
+    }
+
+    /// Runs curl on `url` and returns the status code and body. The session
+    /// goes through stdin so it never shows up in `ps`; a non-empty `form`
+    /// is POSTed.
+    pub(crate) fn request(
+        &mut self,
+        url: &str,
+        form: &[(&str, &str)],
+    ) -> Result<(u32, String), Error> {
+        self.wait();
+        let mut command = Command::new("curl");
+        command.args(["--silent", "--show-error", "--location"]);
+        command.args(["--user-agent", &self.user_agent, "--header", "@-"]);
+        for (name, value) in form {
+            command.args(["--data-urlencode", &format!("{}={}", name, value)]);
+        }
+        command.args(["--write-out", "\n%{http_code}", url]);
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| Error::new(format!("Could not run curl: {}", e)))?;
+        let mut stdin = child.stdin.take().unwrap();
+        let written = stdin.write_all(format!("Cookie: session={}", self.session).as_bytes());
+        drop(stdin);
+        let output = child
+            .wait_with_output()
+            .map_err(|e| Error::new(format!("Could not run curl: {}", e)))?;
+        written.map_err(|e| Error::new(format!("Could not run curl: {}", e)))?;
+        if !output.status.success() {
+            return Err(Error::new(format!(
+                "Request to {} failed: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+        let status = status
+            .trim()
+            .parse()
+            .map_err(|_| Error::new(format!("No HTTP status from {}", url)))?;
+        Ok((status, body.to_string()))
+    }
+
+    /// The input of one puzzle.
+    pub fn fetch(&mut self, year: u32, day: u32) -> Result<String, Error> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        match self.request(&url, &[])? {
+            (200, body) if !body.is_empty() => Ok(body),
+            (200, _) => Err(Error::new(format!("Empty input from {}", url))),
+            (404, _) => Err(Error::new(format!(
+                "No input at {}, is the puzzle unlocked yet?",
+                url
+            ))),
+            (status, body) => Err(Error::new(format!(
+                "HTTP {} from {}: {}",
+                status,
+                url,
+                body.lines().next().unwrap_or("").trim()
+            ))),
+        }
+    }
+
+    /// Writes the input to `path`, or encrypted next to it when there is a
+    /// `key`, unless either already exists. An empty `path`, like the one
+    /// `aoc new` leaves, counts as missing.
+    pub fn download(&mut self, year: u32, day: u32, path: &Path) -> Result<Downloaded, Error> {
+        let encrypted = crypt::encrypted_path(path);
+        if encrypted.exists() {
+            return Ok(Downloaded::Cached(encrypted));
+        }
+        let stub = fs::metadata(path).ok().map(|m| m.len() == 0);
+        if stub == Some(false) {
+            return Ok(Downloaded::Cached(path.to_path_buf()));
+        }
+        let input = self.fetch(year, day)?;
+        let (to, contents) = match &self.key {
+            Some(key) => (encrypted, crypt::encrypt(&input, key)?),
+            None => (path.to_path_buf(), input),
+        };
+        let err =
+            |e: std::io::Error| Error::new(format!("Could not write {}: {}", to.display(), e));
+        if let Some(dir) = to.parent() {
+            fs::create_dir_all(dir).map_err(err)?;
+        }
+        fs::write(&to, contents).map_err(err)?;
+        if stub.is_some() && to != path {
+            // The empty stub would shadow the encrypted input
+            fs::remove_file(path)
+                .map_err(|e| Error::new(format!("Could not remove {}: {}", path.display(), e)))?;
+        }
+        Ok(Downloaded::Fetched(to))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::temp::TempDir;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+
+    /// A stand-in server that answers `responses` in order and returns the
+    /// requests it got, head and body, once they are all answered.
+    pub fn serve(responses: Vec<(u32, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(n) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = n.trim().parse().unwrap();
+                    }
+                    request += &line;
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request += &String::from_utf8(content).unwrap();
+                requests.push(request);
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_download() {
+        let (url, server) = serve(vec![(200, "3   4\n4   3\n"), (404, "Not Found")]);
+        let dir = TempDir::new("download");
+        let path = dir.join("2024/day1.txt");
+        let mut downloader = Downloader::new(&format!("{}/", url), "secret", "me@example.com");
+        downloader.interval = Duration::from_millis(200);
+
+        let start = Instant::now();
+        let fetched = downloader.download(2024, 1, &path);
+        assert_eq!(fetched, Ok(Downloaded::Fetched(path.clone())));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3   4\n4   3\n");
+        // Cached, the server is not asked again
+        let cached = downloader.download(2024, 1, &path);
+        assert_eq!(cached, Ok(Downloaded::Cached(path.clone())));
+        let err = downloader.download(2024, 2, &dir.join("2024/day2.txt"));
+        assert!(err.unwrap_err().message.contains("unlocked"));
+        assert!(!dir.join("2024/day2.txt").exists());
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=secret\r\n"));
+        let agent = format!("User-Agent: {} (+me@example.com)\r\n", USER_AGENT);
+        assert!(requests[0].contains(&agent));
+        assert!(requests[1].starts_with("GET /2024/day/2/input "));
+    }
+
+    #[test]
+    fn test_download_over_stub() {
+        let (url, server) = serve(vec![(200, "1 2\n"), (200, "3 4\n")]);
+        let dir = TempDir::new("download-stub");
+        let mut downloader = Downloader::new(&url, "secret", "me@example.com");
+        downloader.interval = Duration::ZERO;
+
+        // The empty file `aoc new` writes is replaced
+        let path = dir.join("day1.txt");
+        fs::write(&path, "").unwrap();
+        let fetched = downloader.download(2023, 1, &path);
+        assert_eq!(fetched, Ok(Downloaded::Fetched(path.clone())));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n");
+
+        // With a key the input only lands encrypted, and the stub goes
+        let path = dir.join("day2.txt");
+        fs::write(&path, "").unwrap();
+        downloader.key = Some("key".to_string());
+        let encrypted = crypt::encrypted_path(&path);
+        let fetched = downloader.download(2023, 2, &path);
+        assert_eq!(fetched, Ok(Downloaded::Fetched(encrypted.clone())));
+        assert!(!path.exists());
+        let text = fs::read_to_string(&encrypted).unwrap();
+        assert_eq!(crypt::decrypt(&text, "key").unwrap(), "3 4\n");
+        let cached = downloader.download(2023, 2, &path);
+        assert_eq!(cached, Ok(Downloaded::Cached(encrypted)));
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_base_url() {
+        assert_eq!(
+            base_url(Some("http://localhost:8000/")),
+            "http://localhost:8000"
+        );
+    }
+}
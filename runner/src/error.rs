@@ -0,0 +1,135 @@
+use std::fmt;
+
+/// A parse or solve failure, pointing at the input line (1-based) and column
+/// (1-based, in bytes) that caused it where known.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub day: Option<u32>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub text: Option<String>,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Error {
+            day: None,
+            line: None,
+            column: None,
+            text: None,
+            message: message.into(),
+        }
+    }
+
+    /// An error at `fragment`, which must be a slice of `line` (e.g. what a
+    /// `split` or a nom parser left over) so its column can be worked out.
+    /// Anything not wholly inside `line` gets no column.
+    pub fn at(line: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let start = line.as_ptr() as usize;
+        let offset = (fragment.as_ptr() as usize).wrapping_sub(start);
+        let inside = offset
+            .checked_add(fragment.len())
+            .is_some_and(|end| end <= line.len());
+        Error {
+            column: inside.then_some(offset + 1),
+            text: Some(line.to_string()),
+            ..Error::new(message)
+        }
+    }
+
+    pub fn from_nom(line: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                Error::at(line, e.input, format!("expected {:?}", e.code))
+            }
+            nom::Err::Incomplete(_) => Error::new("incomplete input").on_line_text(line),
+        }
+    }
+
+    /// Fills in the line number (1-based) and its text if not already known.
+    pub fn on_line(mut self, line: usize, text: &str) -> Self {
+        self.line.get_or_insert(line);
+        self.on_line_text(text)
+    }
+
+    fn on_line_text(mut self, text: &str) -> Self {
+        self.text.get_or_insert_with(|| text.to_string());
+        self
+    }
+
+    pub fn for_day(mut self, day: u32) -> Self {
+        self.day.get_or_insert(day);
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location: Vec<String> = [
+            self.day.map(|d| format!("day {}", d)),
+            self.line.map(|l| format!("line {}", l)),
+            self.column.map(|c| format!("column {}", c)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(", "))?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(text) = &self.text {
+            write!(f, " in {:?}", text)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parses every line with `parse`, tagging any error with its line number.
+pub fn parse_lines<'a, T>(
+    lines: impl IntoIterator<Item = &'a str>,
+    mut parse: impl FnMut(&'a str) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.on_line(i + 1, line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_of_fragment() {
+        let line = "Game 1: 3 purple";
+        let err = Error::at(line, &line[10..], "unknown color");
+        assert_eq!(err.column, Some(11));
+        // Next to the line in the same text, or running past its end
+        let text = "Game 1: 3 purple\nGame 2: 4 teal";
+        let line = &text[..16];
+        assert_eq!(Error::at(line, &text[17..], "unknown game").column, None);
+        assert_eq!(Error::at(line, &text[10..20], "unknown color").column, None);
+        assert_eq!(
+            Error::at(line, &text[16..16], "expected a color").column,
+            Some(17)
+        );
+    }
+
+    #[test]
+    fn test_parse_lines_reports_line() {
+        let err = parse_lines(["1", "2", "x"], |l| {
+            l.parse::<u32>()
+                .map_err(|_| Error::at(l, l, "not a number"))
+        })
+        .unwrap_err()
+        .for_day(3);
+        assert_eq!(
+            err.to_string(),
+            "day 3, line 3, column 1: not a number in \"x\""
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::answer::Answer;
+use crate::answers::{Answers, Status};
+use crate::error::Error;
+use crate::input::Input;
+use crate::solution::Part;
+use crate::Year;
+
+/// A puzzle example from `examples/`, named after its file: `day8.txt` is
+/// "day8" and `day8-ghosts.txt` is "day8-ghosts".
+#[derive(Debug, Clone, Copy)]
+pub struct Example {
+    pub day: u32,
+    pub name: &'static str,
+    pub input: &'static str,
+}
+
+impl Example {
+    pub fn input(&self) -> Input {
+        Input::new(self.input)
+    }
+}
+
+/// The day an example belongs to, from the `dayN` prefix of its name.
+pub fn example_day(name: &str) -> Option<u32> {
+    let rest = name.strip_prefix("day")?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    match &rest[digits..] {
+        "" => rest.parse().ok(),
+        suffix if suffix.starts_with('-') => rest[..digits].parse().ok(),
+        _ => None,
+    }
+}
+
+/// A year's examples and their expected answers from `examples/answers.txt`.
+pub struct Examples {
+    pub examples: Vec<Example>,
+    pub answers: Answers<String>,
+}
+
+impl Examples {
+    pub fn new(year: &Year) -> Result<Self, Error> {
+        let examples = year
+            .examples
+            .iter()
+            .filter_map(|&(name, input)| {
+                Some(Example {
+                    day: example_day(name)?,
+                    name,
+                    input,
+                })
+            })
+            .collect();
+        Ok(Examples {
+            examples,
+            answers: Answers::parse(year.example_answers)?,
+        })
+    }
+
+    pub fn for_day(&self, day: u32) -> impl Iterator<Item = &Example> {
+        self.examples.iter().filter(move |e| e.day == day)
+    }
+
+    pub fn expected(&self, example: &Example, part: Part) -> Option<&Answer> {
+        self.answers.get(example.name.to_string(), part)
+    }
+
+    pub fn status(&self, example: &Example, part: Part, actual: Option<&Answer>) -> Status {
+        self.answers.status(example.name.to_string(), part, actual)
+    }
+}
+
+/// Runs one example through its day's solution and checks the answer; the
+/// tests generated by `build::register_days` call this.
+pub fn test_example(year: &Year, name: &str, part: Part) {
+    let examples = Examples::new(year).unwrap();
+    let example = examples
+        .examples
+        .iter()
+        .find(|e| e.name == name)
+        .unwrap_or_else(|| panic!("no example named {}", name));
+    let solver = (year.solutions)()
+        .into_iter()
+        .find(|s| s.day() == example.day)
+        .unwrap_or_else(|| panic!("no solution for day {}", example.day));
+    let parsed = solver.parse(&example.input()).unwrap();
+    let actual = solver
+        .solve(parsed.as_ref(), part)
+        .unwrap_or_else(|| panic!("day {} has no part {}", example.day, part))
+        .unwrap();
+    assert_eq!(Some(&actual), examples.expected(example, part));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_day() {
+        assert_eq!(example_day("day8"), Some(8));
+        assert_eq!(example_day("day8-ghosts"), Some(8));
+        assert_eq!(example_day("day12-2"), Some(12));
+        assert_eq!(example_day("day8x"), None);
+        assert_eq!(example_day("answers"), None);
+    }
+}
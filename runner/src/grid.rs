@@ -0,0 +1,529 @@
+use crate::error::Error;
+use crate::input::GridView;
+use crate::point::Point;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A dense, rectangular grid stored row by row. Cells are addressed by
+/// `Point`, `x` being the column and `y` the row, with the origin in the
+/// top left as in `GridView`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// A grid of the given rows, which must all be as wide as the first.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Self, Error> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for row in rows {
+            let expected = *width.get_or_insert(row.len());
+            if row.len() != expected {
+                return Err(Error::new(format!(
+                    "Expected a row of width {}, found {}",
+                    expected,
+                    row.len()
+                )));
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        Ok(Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    /// Parses every character of every line with `parse`, tagging errors with
+    /// their line and column.
+    pub fn parse<'a>(
+        lines: impl IntoIterator<Item = &'a str>,
+        mut parse: impl FnMut(char) -> Result<T, Error>,
+    ) -> Result<Self, Error> {
+        let mut rows = Vec::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            let row = line
+                .char_indices()
+                .map(|(j, c)| {
+                    parse(c)
+                        .map_err(|e| Error::at(line, &line[j..], e.message).on_line(i + 1, line))
+                })
+                .collect::<Result<Vec<T>, Error>>()?;
+            if rows
+                .first()
+                .is_some_and(|first: &Vec<T>| first.len() != row.len())
+            {
+                return Err(Error::new(format!(
+                    "Expected a row of width {}, found {}",
+                    rows[0].len(),
+                    row.len()
+                ))
+                .on_line(i + 1, line));
+            }
+            rows.push(row);
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset(&self, p: Point) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    fn point(&self, offset: usize) -> Point {
+        Point::new((offset % self.width) as i64, (offset / self.width) as i64)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Replaces the cell at `p`, returning the old value, or `None` (and
+    /// dropping `value`) when it is out of bounds.
+    pub fn set(&mut self, p: Point, value: T) -> Option<T> {
+        self.get_mut(p).map(|cell| std::mem::replace(cell, value))
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (0..self.width as i64).contains(&p.x) && (0..self.height as i64).contains(&p.y)
+    }
+
+    /// The up to 4 orthogonal neighbors of `p` inside the grid.
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbors4().into_iter().filter(|&n| self.contains(n))
+    }
+
+    /// The up to 8 neighbors of `p` inside the grid, diagonals included.
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbors8().into_iter().filter(|&n| self.contains(n))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        let start = y.checked_mul(self.width)?;
+        (y < self.height).then(|| &self.cells[start..start + self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() panics on 0, and a zero-width grid has no rows to give
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Cells from `p` towards the bottom right.
+    pub fn diagonal(&self, p: Point) -> impl Iterator<Item = &T> {
+        (0..).map_while(move |i| self.get(p + Point::new(i, i)))
+    }
+
+    /// Cells from `p` towards the bottom left.
+    pub fn anti_diagonal(&self, p: Point) -> impl Iterator<Item = &T> {
+        (0..).map_while(move |i| self.get(p + Point::new(-i, i)))
+    }
+
+    /// Every top left to bottom right diagonal, starting in the bottom left
+    /// corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height as i64)
+            .rev()
+            .map(|y| Point::new(0, y))
+            .chain((1..self.width as i64).map(|x| Point::new(x, 0)));
+        starts.map(move |p| self.diagonal(p))
+    }
+
+    /// Every top right to bottom left diagonal, starting in the top left
+    /// corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let right = self.width as i64 - 1;
+        let starts = (0..self.width as i64)
+            .map(|x| Point::new(x, 0))
+            .chain((1..self.height as i64).map(move |y| Point::new(right, y)));
+        starts.map(move |p| self.anti_diagonal(p))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.cells.len()).map(|i| self.point(i))
+    }
+
+    /// Every position and its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position, row by row, whose cell matches.
+    pub fn find(&self, mut matches: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, cell)| matches(cell)).map(|(p, _)| p)
+    }
+
+    /// Every position holding `value`.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, c)| *c == value)
+            .map(|(p, _)| p)
+    }
+
+    /// The runs of consecutive matching cells, row by row. A run ends with
+    /// its row, so a match in the last column never continues into the next
+    /// row.
+    pub fn spans<F: FnMut(&T) -> bool>(&self, matches: F) -> Spans<'_, T, F> {
+        Spans {
+            grid: self,
+            matches,
+            next: 0,
+        }
+    }
+
+    /// The cells of `span`.
+    pub fn span(&self, span: &Span) -> &[T] {
+        let start = self.offset(span.start).expect("span inside the grid");
+        let end = self.offset(span.end).expect("span inside the grid");
+        &self.cells[start..=end]
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// A `width` x `height` grid taking each cell from `source(x, y)` of this
+    /// one.
+    fn rearranged(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..width * height)
+            .map(|i| {
+                let (x, y) = source(i % width, i / width);
+                self.cells[y * self.width + x].clone()
+            })
+            .collect();
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// A quarter turn clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearranged(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    /// A quarter turn counterclockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearranged(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Mirrored left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearranged(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    /// Mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearranged(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+}
+
+/// A horizontal run of cells, `start` to `end` inclusive, within one row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Span {
+    /// Number of cells, at least 1.
+    pub fn width(&self) -> usize {
+        (self.end.x - self.start.x + 1) as usize
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.y == self.start.y && (self.start.x..=self.end.x).contains(&p.x)
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let y = self.start.y;
+        (self.start.x..=self.end.x).map(move |x| Point::new(x, y))
+    }
+
+    /// The ring of cells around the span, diagonals included, whether or not
+    /// they are inside the grid.
+    pub fn neighbors(&self) -> impl Iterator<Item = Point> {
+        let (start, end) = (self.start, self.end);
+        let above_below = (start.x - 1..=end.x + 1)
+            .flat_map(move |x| [Point::new(x, start.y - 1), Point::new(x, start.y + 1)]);
+        [
+            Point::new(start.x - 1, start.y),
+            Point::new(end.x + 1, end.y),
+        ]
+        .into_iter()
+        .chain(above_below)
+    }
+}
+
+/// Iterator of `Grid::spans`.
+pub struct Spans<'a, T, F> {
+    grid: &'a Grid<T>,
+    matches: F,
+    next: usize,
+}
+
+impl<T, F: FnMut(&T) -> bool> Iterator for Spans<'_, T, F> {
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Span> {
+        let cells = &self.grid.cells;
+        while self.next < cells.len() && !(self.matches)(&cells[self.next]) {
+            self.next += 1;
+        }
+        if self.next >= cells.len() {
+            return None;
+        }
+        let start = self.next;
+        let row_end = (start / self.grid.width + 1) * self.grid.width;
+        while self.next < row_end && (self.matches)(&cells[self.next]) {
+            self.next += 1;
+        }
+        Some(Span {
+            start: self.grid.point(start),
+            end: self.grid.point(self.next - 1),
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{} is outside the {}x{} grid", p, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{} is outside the {}x{} grid", p, width, height))
+    }
+}
+
+impl From<GridView<'_>> for Grid<u8> {
+    fn from(view: GridView) -> Self {
+        Grid {
+            cells: view.rows().flatten().copied().collect(),
+            width: view.width(),
+            height: view.height(),
+        }
+    }
+}
+
+/// One line per row, the cells written back to back.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
+
+    fn grid(text: &str) -> Grid<char> {
+        Grid::parse(text.lines(), Ok).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = grid("ab\ncd\nef");
+        assert_eq!((g.width(), g.height()), (2, 3));
+        assert_eq!(g.get(Point::new(1, 2)), Some(&'f'));
+        assert_eq!(g.get(Point::new(2, 0)), None);
+        assert_eq!(g.get(Point::new(-1, 0)), None);
+        assert_eq!(g.row(1), Some(&['c', 'd'][..]));
+        assert_eq!(g.to_string(), "ab\ncd\nef");
+
+        let err = Grid::parse(["12", "3x"], |c| {
+            c.to_digit(10).ok_or_else(|| Error::new("not a digit"))
+        })
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(2)));
+        assert_eq!(Grid::parse(["12", "3"], Ok).unwrap_err().line, Some(2));
+
+        let input = Input::new("#.\n.#");
+        assert_eq!(
+            Grid::from(input.grid().unwrap()).map(|&b| b as char),
+            grid("#.\n.#")
+        );
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut g = Grid::new(3, 2, 0);
+        assert_eq!(g.set(Point::new(2, 1), 5), Some(0));
+        assert_eq!(g.set(Point::new(3, 1), 5), None);
+        g[Point::ORIGIN] = 1;
+        assert_eq!(g.to_string(), "100\n005");
+        assert_eq!(g.find(|&c| c == 5), Some(Point::new(2, 1)));
+        assert_eq!(g.positions_of(&0).count(), 4);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let g = Grid::new(3, 3, ());
+        assert_eq!(g.neighbors4(Point::new(1, 1)).count(), 4);
+        assert_eq!(g.neighbors8(Point::new(1, 1)).count(), 8);
+        assert_eq!(
+            g.neighbors4(Point::ORIGIN).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(
+            g.neighbors8(Point::new(2, 2)).collect::<Vec<_>>(),
+            [Point::new(2, 1), Point::new(1, 2), Point::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let g = grid("abc\ndef");
+        let text = |cells: &mut dyn Iterator<Item = &char>| cells.collect::<String>();
+        let columns: Vec<String> = g.columns().map(|mut c| text(&mut c)).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        let diagonals: Vec<String> = g.diagonals().map(|mut d| text(&mut d)).collect();
+        assert_eq!(diagonals, ["d", "ae", "bf", "c"]);
+        let anti: Vec<String> = g.anti_diagonals().map(|mut d| text(&mut d)).collect();
+        assert_eq!(anti, ["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn test_transforms() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(g.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(g.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(g.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(g.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(g.rotate_right().rotate_left(), g);
+    }
+
+    #[test]
+    fn test_spans() {
+        // Runs at both edges, one continuing on the next row, a single cell
+        // and one ending in the last cell
+        let g = grid("12..3\n4.5.6\n...78");
+        let spans: Vec<Span> = g.spans(char::is_ascii_digit).collect();
+        let text: Vec<String> = spans.iter().map(|s| g.span(s).iter().collect()).collect();
+        assert_eq!(text, ["12", "3", "4", "5", "6", "78"]);
+        assert_eq!(
+            spans[1],
+            Span {
+                start: Point::new(4, 0),
+                end: Point::new(4, 0),
+            }
+        );
+        assert_eq!(spans[5].start, Point::new(3, 2));
+        assert_eq!(spans[5].width(), 2);
+        assert!(spans[0].contains(Point::new(1, 0)));
+        assert!(!spans[0].contains(Point::new(2, 0)));
+
+        assert_eq!(grid("123").spans(|c| *c == '1').count(), 1);
+        assert_eq!(grid("...\n...").spans(char::is_ascii_digit).count(), 0);
+        assert_eq!(Grid::<char>::new(0, 0, '.').spans(|_| true).count(), 0);
+    }
+
+    #[test]
+    fn test_span_neighbors() {
+        let span = Span {
+            start: Point::new(1, 1),
+            end: Point::new(2, 1),
+        };
+        let mut neighbors: Vec<Point> = span.neighbors().collect();
+        neighbors.sort();
+        let mut expected: Vec<Point> = Grid::new(4, 3, ())
+            .positions()
+            .filter(|&p| !span.contains(p))
+            .collect();
+        expected.sort();
+        assert_eq!(neighbors, expected);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::bench::Timing;
+use crate::error::Error;
+use crate::solution::Part;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Where `--bench` appends its estimates, relative to the working directory.
+pub const HISTORY_FILE: &str = "bench_output.txt";
+
+/// The rustc that built this binary, from `build.rs`.
+pub const RUSTC_VERSION: &str = env!("AOC_RUSTC_VERSION");
+
+const HEADER: &str = "# timestamp commit rustc year day part beta_ns std_error_ns r2 iterations";
+
+/// Two-sided 95% quantile of the normal distribution.
+const Z_95: f64 = 1.96;
+
+/// One part's `--bench` estimate, a line of `bench_output.txt`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Seconds since the epoch, shared by every entry of a run.
+    pub timestamp: u64,
+    /// `git rev-parse --short HEAD`, with `-dirty` for uncommitted changes.
+    pub commit: String,
+    pub rustc: String,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub beta_ns: f64,
+    pub std_error_ns: f64,
+    pub r2: f64,
+    pub iterations: u64,
+}
+
+impl Entry {
+    pub fn new(run: &Run, year: u32, day: u32, part: Part, timing: &Timing) -> Self {
+        Entry {
+            timestamp: run.timestamp,
+            commit: run.commit.clone(),
+            rustc: RUSTC_VERSION.to_string(),
+            year,
+            day,
+            part: part as u32,
+            beta_ns: timing.per_iteration.as_secs_f64() * 1e9,
+            std_error_ns: timing.std_error.map_or(0.0, |e| e.as_secs_f64() * 1e9),
+            r2: timing.r2.unwrap_or(0.0),
+            iterations: timing.iterations,
+        }
+    }
+
+    fn parse(line: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [timestamp, commit, rustc, year, day, part, beta_ns, std_error_ns, r2, iterations] =
+            fields[..]
+        else {
+            return Err(format!("Expected 10 fields, found {}", fields.len()));
+        };
+        fn number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+            value
+                .parse()
+                .map_err(|_| format!("Invalid {} {:?}", name, value))
+        }
+        // Estimates become `Duration`s, which must be finite and not negative
+        fn nanos(name: &str, value: &str) -> Result<f64, String> {
+            match number::<f64>(name, value)? {
+                ns if ns.is_finite() && ns >= 0.0 => Ok(ns),
+                _ => Err(format!("Invalid {} {:?}", name, value)),
+            }
+        }
+        Ok(Entry {
+            timestamp: number("timestamp", timestamp)?,
+            commit: commit.to_string(),
+            rustc: rustc.to_string(),
+            year: number("year", year)?,
+            day: number("day", day)?,
+            part: number("part", part)?,
+            beta_ns: nanos("beta_ns", beta_ns)?,
+            std_error_ns: nanos("std_error_ns", std_error_ns)?,
+            r2: number("r2", r2)?,
+            iterations: number("iterations", iterations)?,
+        })
+    }
+
+    fn key(&self) -> (u32, u32, u32) {
+        (self.year, self.day, self.part)
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} {:.1} {:.1} {:.4} {}",
+            self.timestamp,
+            self.commit,
+            self.rustc,
+            self.year,
+            self.day,
+            self.part,
+            self.beta_ns,
+            self.std_error_ns,
+            self.r2,
+            self.iterations
+        )
+    }
+}
+
+/// What every entry of one `--bench` run shares.
+pub struct Run {
+    pub timestamp: u64,
+    pub commit: String,
+}
+
+impl Run {
+    pub fn now() -> Self {
+        Run {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit: commit(),
+        }
+    }
+}
+
+/// Runs git on this repository, wherever `aoc` was started from.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(["-C", env!("CARGO_MANIFEST_DIR")])
+        .args(args)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn commit() -> String {
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash)
+            if git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|s| !s.is_empty()) =>
+        {
+            format!("{}-dirty", hash)
+        }
+        Some(hash) => hash,
+        None => "unknown".to_string(),
+    }
+}
+
+/// Every entry of `bench_output.txt`, oldest first.
+#[derive(Debug, Default)]
+pub struct History {
+    pub entries: Vec<Entry>,
+}
+
+impl History {
+    /// Reads the history at `path`; a missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(History::default()),
+            Err(e) => {
+                return Err(Error::new(format!(
+                    "Could not read {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+        let entries = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'))
+            .map(|(i, l)| Entry::parse(l).map_err(|e| Error::new(e).on_line(i + 1, l)))
+            .collect::<Result<_, _>>()?;
+        Ok(History { entries })
+    }
+
+    pub fn append(path: &Path, entries: &[Entry]) -> Result<(), Error> {
+        let err = |e: io::Error| Error::new(format!("Could not write {}: {}", path.display(), e));
+        let new = !path.exists();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(err)?;
+        if new {
+            writeln!(file, "{}", HEADER).map_err(err)?;
+        }
+        for entry in entries {
+            writeln!(file, "{}", entry).map_err(err)?;
+        }
+        Ok(())
+    }
+
+    /// The entries of `name`: `last` for the most recent run, otherwise a
+    /// commit hash or prefix of one. Later runs of a commit win.
+    pub fn baseline(&self, name: &str) -> Result<Baseline, Error> {
+        let matches: Vec<&Entry> = if name == "last" {
+            let last = self.entries.iter().map(|e| e.timestamp).max();
+            self.entries
+                .iter()
+                .filter(|e| Some(e.timestamp) == last)
+                .collect()
+        } else {
+            self.entries
+                .iter()
+                .filter(|e| e.commit.starts_with(name))
+                .collect()
+        };
+        if matches.is_empty() {
+            return Err(Error::new(format!(
+                "No benchmark history for baseline {:?}",
+                name
+            )));
+        }
+        let entries = matches.into_iter().map(|e| (e.key(), e.clone())).collect();
+        Ok(Baseline {
+            name: name.to_string(),
+            entries,
+        })
+    }
+}
+
+/// The estimates `--compare` checks a run against.
+pub struct Baseline {
+    pub name: String,
+    entries: HashMap<(u32, u32, u32), Entry>,
+}
+
+impl Baseline {
+    pub fn compare(&self, entry: &Entry, threshold: f64) -> Option<Comparison> {
+        let base = self.entries.get(&entry.key())?;
+        // A zero estimate has nothing to be relative to
+        let change = match base.beta_ns > 0.0 {
+            true => entry.beta_ns / base.beta_ns - 1.0,
+            false => 0.0,
+        };
+        // Fewer than 3 samples leave the standard error at 0, unknown
+        let known = entry.std_error_ns > 0.0 && base.std_error_ns > 0.0;
+        // The difference of two estimates has the combined standard error
+        let noise = Z_95 * (entry.std_error_ns.powi(2) + base.std_error_ns.powi(2)).sqrt();
+        let slower = entry.beta_ns - base.beta_ns;
+        Some(Comparison {
+            baseline: Duration::from_secs_f64(base.beta_ns / 1e9),
+            change,
+            regression: known.then_some(slower > noise && change > threshold),
+        })
+    }
+}
+
+/// A part's estimate relative to its baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub baseline: Duration,
+    /// Relative change, `0.1` is 10% slower.
+    pub change: f64,
+    /// Slower than the baseline by more than both its 95% interval and the
+    /// threshold, `None` when either estimate's noise is unknown.
+    pub regression: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: u64, commit: &str, beta_ns: f64, std_error_ns: f64) -> Entry {
+        Entry {
+            timestamp,
+            commit: commit.to_string(),
+            rustc: "1.80.0".to_string(),
+            year: 2023,
+            day: 1,
+            part: 1,
+            beta_ns,
+            std_error_ns,
+            r2: 0.99,
+            iterations: 1000,
+        }
+    }
+
+    #[test]
+    fn test_entry_round_trip() {
+        let e = entry(1700000000, "abc1234-dirty", 1234.5, 6.7);
+        assert_eq!(Entry::parse(&e.to_string()), Ok(e));
+        assert!(Entry::parse("1 abc 1.80.0 2023 1").is_err());
+        for beta in ["nan", "inf", "-5.0"] {
+            let line = format!("1 abc 1.80.0 2023 1 1 {} 1.0 0.99 100", beta);
+            assert!(Entry::parse(&line).is_err(), "{}", beta);
+        }
+    }
+
+    #[test]
+    fn test_baseline() {
+        let history = History {
+            entries: vec![
+                entry(1, "abc1234", 1000.0, 10.0),
+                entry(2, "def5678", 900.0, 10.0),
+            ],
+        };
+        let last = history.baseline("last").unwrap();
+        assert_eq!(last.entries[&(2023, 1, 1)].commit, "def5678");
+        let abc = history.baseline("abc").unwrap();
+        assert_eq!(abc.entries[&(2023, 1, 1)].beta_ns, 1000.0);
+        assert!(history.baseline("fff").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let history = History {
+            entries: vec![entry(1, "abc1234", 1000.0, 10.0)],
+        };
+        let baseline = history.baseline("abc").unwrap();
+        // 2% slower is within the threshold
+        let c = baseline
+            .compare(&entry(2, "new", 1020.0, 1.0), 0.05)
+            .unwrap();
+        assert_eq!(c.regression, Some(false));
+        // 20% slower but far noisier than the difference
+        let c = baseline
+            .compare(&entry(2, "new", 1200.0, 500.0), 0.05)
+            .unwrap();
+        assert_eq!(c.regression, Some(false));
+        let c = baseline
+            .compare(&entry(2, "new", 1200.0, 10.0), 0.05)
+            .unwrap();
+        assert_eq!(c.regression, Some(true));
+        assert!((c.change - 0.2).abs() < 1e-9);
+        // Too few samples to know the noise
+        let c = baseline
+            .compare(&entry(2, "new", 1200.0, 0.0), 0.05)
+            .unwrap();
+        assert_eq!(c.regression, None);
+        let mut other = entry(2, "new", 1200.0, 10.0);
+        other.day = 2;
+        assert_eq!(baseline.compare(&other, 0.05), None);
+    }
+
+    #[test]
+    fn test_compare_zero_baseline() {
+        let history = History {
+            entries: vec![entry(1, "abc1234", 0.0, 10.0)],
+        };
+        let baseline = history.baseline("abc").unwrap();
+        let c = baseline
+            .compare(&entry(2, "new", 1200.0, 10.0), 0.05)
+            .unwrap();
+        assert_eq!(c.change, 0.0);
+        assert_eq!(c.regression, Some(false));
+    }
+}
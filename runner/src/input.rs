@@ -0,0 +1,139 @@
+use crate::error::Error;
+
+/// A puzzle input as handed to `Solution::parse`.
+///
+/// Line endings are normalized to `\n`, trailing whitespace is removed from
+/// every line and trailing blank lines are dropped, so `lines()` never yields
+/// a stray empty line or a `\r`. The accessors borrow from the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    pub fn new(text: &str) -> Self {
+        let mut normalized = String::with_capacity(text.len());
+        for line in text.lines() {
+            normalized.push_str(line.trim_end());
+            normalized.push('\n');
+        }
+        normalized.truncate(normalized.trim_end_matches('\n').len());
+        Input { text: normalized }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    /// Blocks of lines separated by one or more blank lines.
+    pub fn paragraphs(&self) -> impl Iterator<Item = &str> {
+        self.text
+            .split("\n\n")
+            .map(|p| p.trim_start_matches('\n'))
+            .filter(|p| !p.is_empty())
+    }
+
+    /// The input as a rectangular grid of bytes.
+    pub fn grid(&self) -> Result<GridView<'_>, Error> {
+        let width = self.lines().next().map_or(0, str::len);
+        for (i, line) in self.lines().enumerate() {
+            if line.len() != width {
+                return Err(Error::new(format!(
+                    "Expected a row of width {}, found {}",
+                    width,
+                    line.len()
+                ))
+                .on_line(i + 1, line));
+            }
+        }
+        Ok(GridView {
+            text: self.text.as_bytes(),
+            width,
+            height: self.lines().count(),
+        })
+    }
+
+    /// The 1-based line number of `fragment`, which must be a slice of this
+    /// input (e.g. a line of one of the `paragraphs()`).
+    pub fn line_number(&self, fragment: &str) -> Option<usize> {
+        let offset = (fragment.as_ptr() as usize).checked_sub(self.text.as_ptr() as usize)?;
+        let before = self.text.get(..offset)?;
+        Some(before.matches('\n').count() + 1)
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Input::new(text)
+    }
+}
+
+/// A borrowed, rectangular view of an `Input`; `(x, y)` is (column, row).
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a> {
+    text: &'a [u8],
+    width: usize,
+    height: usize,
+}
+
+impl<'a> GridView<'a> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn row(&self, y: usize) -> Option<&'a [u8]> {
+        let start = y.checked_mul(self.width + 1)?;
+        (y < self.height).then(|| &self.text[start..start + self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        self.row(y)?.get(x).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalizes_line_endings() {
+        let input = Input::new("ab  \r\ncd\r\n\r\n");
+        assert_eq!(input.as_str(), "ab\ncd");
+        assert_eq!(input.lines().collect::<Vec<_>>(), ["ab", "cd"]);
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let input = Input::new("LR\n\nA\nB\n\n\nC\n");
+        assert_eq!(input.paragraphs().collect::<Vec<_>>(), ["LR", "A\nB", "C"]);
+        let c = input.paragraphs().last().unwrap();
+        assert_eq!(input.line_number(c), Some(7));
+        assert_eq!(input.line_number("C"), None);
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = Input::new("#.\n.#\n");
+        let grid = input.grid().unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.get(1, 1), Some(b'#'));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.row(1), Some(&b".#"[..]));
+        assert_eq!(grid.rows().count(), 2);
+
+        let err = Input::new("##\n#\n").grid().unwrap_err();
+        assert_eq!(err.line, Some(2));
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Runs `work` on every job using `threads` threads, handing the results to
+/// `done` on the calling thread in the order of `jobs`.
+pub fn run_ordered<J: Sync, T: Send>(
+    jobs: &[J],
+    threads: usize,
+    work: impl Fn(&J) -> T + Sync,
+    mut done: impl FnMut(&J, T),
+) {
+    if threads <= 1 {
+        for job in jobs {
+            done(job, work(job));
+        }
+        return;
+    }
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads.min(jobs.len()) {
+            let (tx, next, work) = (tx.clone(), &next, &work);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(i) else {
+                    break;
+                };
+                if tx.send((i, work(job))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+        // Hold back results until every earlier job is done
+        let mut pending = BTreeMap::new();
+        let mut printed = 0;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&printed) {
+                done(&jobs[printed], result);
+                printed += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_run_ordered() {
+        let jobs: Vec<u64> = (0..20).collect();
+        let mut seen = Vec::new();
+        run_ordered(
+            &jobs,
+            4,
+            |&j| {
+                // Later jobs finish first
+                thread::sleep(Duration::from_millis(20 - j));
+                j * 2
+            },
+            |&j, result| seen.push((j, result)),
+        );
+        let expected: Vec<(u64, u64)> = jobs.iter().map(|&j| (j, j * 2)).collect();
+        assert_eq!(seen, expected);
+    }
+}
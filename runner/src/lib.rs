@@ -0,0 +1,748 @@
+mod answer;
+mod answers;
+pub mod bench;
+pub mod build;
+mod crypt;
+mod download;
+mod error;
+mod examples;
+mod grid;
+mod history;
+mod input;
+mod jobs;
+mod memory;
+mod output;
+mod point;
+mod readme;
+mod scaffold;
+mod solution;
+mod source;
+mod submit;
+#[cfg(test)]
+mod temp;
+mod visualize;
+mod watch;
+
+pub use answer::Answer;
+pub use answers::{Answers, Status};
+pub use crypt::{decrypt, encrypt, encrypted_path, KEY_VAR};
+pub use download::{Downloaded, Downloader, BASE_URL_VAR, CONTACT_VAR, SESSION_VAR};
+pub use error::{parse_lines, Error};
+pub use examples::{test_example, Example, Examples};
+pub use grid::{Grid, Span, Spans};
+pub use history::{Baseline, Comparison, Entry, History, Run, HISTORY_FILE};
+pub use input::{GridView, Input};
+pub use memory::{Allocations, Counting};
+pub use output::{Format, Output, Row};
+pub use point::{Direction, Point, Turn};
+pub use readme::AnswerStyle;
+pub use scaffold::new_day;
+pub use solution::{register, Part, Solution, Solver};
+pub use source::{load_file, year_data_dir, Source, DATA_DIR_VAR};
+pub use submit::{parse_response, Outcome, Submission, SubmissionLog};
+pub use visualize::{Painter, Style};
+
+use bench::{BenchOptions, Timing};
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+use strum::IntoEnumIterator;
+
+/// One crate of day modules, e.g. `aoc2023::YEAR`.
+pub struct Year {
+    pub year: u32,
+    pub solutions: fn() -> Vec<Box<dyn Solver>>,
+    /// Default directory of `dayN.txt` inputs, usually
+    /// `concat!(env!("CARGO_MANIFEST_DIR"), "/data")`.
+    pub data_dir: &'static str,
+    /// Contents of the year's `answers.txt`, see `Answers`.
+    pub answers: &'static str,
+    /// `(name, input)` for every file in `examples/`, from `build.rs`.
+    pub examples: &'static [(&'static str, &'static str)],
+    /// Contents of `examples/answers.txt`, keyed by example name.
+    pub example_answers: &'static str,
+}
+
+/// Runs `f` for its result and times it, repeatedly with `--bench` when
+/// `ok` accepts the result. Allocations are counted on the first run.
+fn timed<T>(
+    options: &BenchOptions,
+    mut f: impl FnMut() -> T,
+    ok: impl Fn(&T) -> bool,
+) -> (T, Timing, Allocations) {
+    let ((result, elapsed), allocations) = memory::count(|| bench::once(&mut f));
+    let timing = if options.bench && ok(&result) {
+        bench::measure(options, f)
+    } else {
+        Timing {
+            iterations: 1,
+            per_iteration: elapsed,
+            r2: None,
+            std_error: None,
+        }
+    };
+    (result, timing, allocations)
+}
+
+// CLI stuff
+
+#[derive(Parser, Debug, Clone)]
+#[clap(version = "0.1", about = "Advent of Code")]
+struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
+    /// Only run this year, e.g. 2023 (default: all years)
+    #[clap(short, long)]
+    year: Option<u32>,
+    #[clap(short, long)]
+    day: Option<u32>,
+    #[clap(short, long)]
+    part: Option<u32>,
+    /// Compare each answer against answers.txt and exit non-zero on a mismatch
+    #[clap(long)]
+    check: bool,
+    /// Run the examples in examples/ instead of the puzzle input, checking
+    /// them against examples/answers.txt
+    #[clap(long, conflicts_with = "input")]
+    example: bool,
+    /// Read the puzzle input from this file instead, or `-` for stdin. Its
+    /// answers are not checked against answers.txt
+    #[clap(short, long, requires = "year", requires = "day")]
+    input: Option<PathBuf>,
+    /// Read inputs from <DATA_DIR>/<year>/dayN.txt [env: AOC_DATA_DIR]
+    #[clap(long)]
+    data_dir: Option<PathBuf>,
+    /// Solve this many inputs at once, 0 for one per core (ignored with --bench)
+    #[clap(short, long, value_name = "N", default_value_t = 1)]
+    jobs: usize,
+    /// Rerun whenever the day's input, examples or source change
+    #[clap(long, requires = "day")]
+    watch: bool,
+    /// With --watch, don't rebuild when the source or examples change
+    #[clap(long, requires = "watch")]
+    no_rebuild: bool,
+    /// Also draw the parsed input, in color on a terminal, for days that can.
+    /// Drawn to stderr unless the format is a table, to keep the results
+    /// parseable
+    #[clap(long, requires = "day")]
+    visualize: bool,
+    /// How to print the results
+    #[clap(long, value_enum, default_value_t)]
+    format: Format,
+    #[clap(flatten)]
+    bench: BenchOptions,
+    /// Append --bench estimates of puzzle inputs to this file
+    #[clap(long, value_name = "PATH", default_value = HISTORY_FILE)]
+    history: PathBuf,
+    /// Check --bench estimates against a commit in the history, or `last`
+    #[clap(long, value_name = "BASELINE", requires = "bench")]
+    compare: Option<String>,
+    /// How much slower than the baseline, in percent, a part may get beyond
+    /// the noise before --compare fails
+    #[clap(
+        long,
+        value_name = "PERCENT",
+        default_value_t = 5.0,
+        requires = "compare"
+    )]
+    threshold: f64,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// Create src/dayN.rs, examples/dayN.txt and an empty input for a new day
+    New {
+        #[clap(long)]
+        year: u32,
+        #[clap(long)]
+        day: u32,
+        /// Write the input stub to <DATA_DIR>/<year>/dayN.txt [env: AOC_DATA_DIR]
+        #[clap(long)]
+        data_dir: Option<PathBuf>,
+    },
+    /// Download missing puzzle inputs into the data directory, using the
+    /// session token from $AOC_SESSION or ~/.config/aoc/session. Requests
+    /// name the contact from $AOC_CONTACT or ~/.config/aoc/contact, and
+    /// inputs are written encrypted when there is an input key
+    Download {
+        #[clap(long)]
+        year: u32,
+        /// Only this day (default: every day of the year)
+        #[clap(long)]
+        day: Option<u32>,
+        /// Server to download from [env: AOC_BASE_URL]
+        #[clap(long)]
+        base_url: Option<String>,
+        /// Write inputs to <DATA_DIR>/<year>/dayN.txt [env: AOC_DATA_DIR]
+        #[clap(long)]
+        data_dir: Option<PathBuf>,
+    },
+    /// Solve a part of the puzzle input and submit the answer, unless
+    /// <data dir>/submissions.txt shows it can't be right. Needs the same
+    /// session and contact as download
+    Submit {
+        /// The year (default: the latest one)
+        #[clap(long)]
+        year: Option<u32>,
+        #[clap(long)]
+        day: u32,
+        #[clap(long)]
+        part: u32,
+        /// Server to submit to [env: AOC_BASE_URL]
+        #[clap(long)]
+        base_url: Option<String>,
+        /// Read inputs from <DATA_DIR>/<year>/dayN.txt [env: AOC_DATA_DIR]
+        #[clap(long)]
+        data_dir: Option<PathBuf>,
+    },
+    /// Replace the plain dayN.txt inputs with dayN.txt.enc, encrypted with
+    /// the key from $AOC_INPUT_KEY or ~/.config/aoc/input-key
+    Encrypt {
+        /// Only this year (default: every year)
+        #[clap(long)]
+        year: Option<u32>,
+        /// Inputs are in <DATA_DIR>/<year>/ [env: AOC_DATA_DIR]
+        #[clap(long)]
+        data_dir: Option<PathBuf>,
+    },
+    /// Replace the encrypted dayN.txt.enc inputs with plain dayN.txt
+    Decrypt {
+        /// Only this year (default: every year)
+        #[clap(long)]
+        year: Option<u32>,
+        /// Inputs are in <DATA_DIR>/<year>/ [env: AOC_DATA_DIR]
+        #[clap(long)]
+        data_dir: Option<PathBuf>,
+    },
+    /// Solve every year and rewrite the results region of the README with
+    /// stars, answers and median timings
+    Readme {
+        #[clap(long, default_value = "README.md")]
+        path: PathBuf,
+        /// How to show the answers
+        #[clap(long, value_enum, default_value_t)]
+        answers: AnswerStyle,
+        /// Solve each part this many times for the median
+        #[clap(long, value_name = "N", default_value_t = 5)]
+        runs: usize,
+        /// Read inputs from <DATA_DIR>/<year>/dayN.txt [env: AOC_DATA_DIR]
+        #[clap(long)]
+        data_dir: Option<PathBuf>,
+    },
+}
+
+/// Solves one part of a day's puzzle input.
+fn solve_input(
+    year: &Year,
+    day: u32,
+    part: Part,
+    data_dir: Option<&Path>,
+) -> std::result::Result<Answer, Error> {
+    let solver = (year.solutions)()
+        .into_iter()
+        .find(|s| s.day() == day)
+        .ok_or_else(|| Error::new(format!("No solution for day {}", day)))?;
+    let input = Source::day(year, day, data_dir).load()?;
+    let parsed = solver.parse(&input)?;
+    solver
+        .solve(parsed.as_ref(), part)
+        .ok_or_else(|| Error::new(format!("Day {} has no part {}", day, part)))?
+        .map_err(|e| e.for_day(day))
+}
+
+fn find_year(years: &[Year], year: u32) -> Option<&Year> {
+    let found = years.iter().find(|y| y.year == year);
+    if found.is_none() {
+        eprintln!("No crate for year {}", year);
+    }
+    found
+}
+
+fn run_command(years: &[Year], command: &Command) -> ExitCode {
+    match command {
+        Command::New {
+            year,
+            day,
+            data_dir,
+        } => {
+            let Some(year) = find_year(years, *year) else {
+                return ExitCode::FAILURE;
+            };
+            match new_day(year, *day, data_dir.as_deref()) {
+                Ok(files) => {
+                    for file in files {
+                        println!("Created {}", file.display());
+                    }
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Download {
+            year,
+            day,
+            base_url,
+            data_dir,
+        } => {
+            let Some(year) = find_year(years, *year) else {
+                return ExitCode::FAILURE;
+            };
+            let (session, contact) = match download::session_token()
+                .and_then(|session| Ok((session, download::contact()?)))
+            {
+                Ok(settings) => settings,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let base_url = download::base_url(base_url.as_deref());
+            let mut downloader = Downloader::new(&base_url, &session, &contact);
+            downloader.key = crypt::key().ok();
+            let days: Vec<u32> = match day {
+                Some(day) => vec![*day],
+                None => (year.solutions)().iter().map(|s| s.day()).collect(),
+            };
+            let dir = year_data_dir(year, data_dir.as_deref());
+            let mut failed = false;
+            for day in days {
+                let path = dir.join(format!("day{}.txt", day));
+                match downloader.download(year.year, day, &path) {
+                    Ok(Downloaded::Cached(path)) => {
+                        println!("day {}: have {}", day, path.display())
+                    }
+                    Ok(Downloaded::Fetched(path)) => {
+                        println!("day {}: downloaded {}", day, path.display())
+                    }
+                    Err(e) => {
+                        eprintln!("{}", e.for_day(day));
+                        failed = true;
+                    }
+                }
+            }
+            if failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            base_url,
+            data_dir,
+        } => {
+            let year = match year {
+                Some(year) => find_year(years, *year),
+                None => years.iter().max_by_key(|y| y.year),
+            };
+            let Some(year) = year else {
+                return ExitCode::FAILURE;
+            };
+            let Some(part) = Part::iter().find(|p| p == part) else {
+                eprintln!("Part must be 1 or 2");
+                return ExitCode::FAILURE;
+            };
+            let submitted = solve_input(year, *day, part, data_dir.as_deref()).and_then(|answer| {
+                println!("{} day {} part {}: {}", year.year, day, part, answer);
+                let path = year_data_dir(year, data_dir.as_deref()).join("submissions.txt");
+                let mut log = SubmissionLog::load(&path)?;
+                let session = download::session_token()?;
+                let contact = download::contact()?;
+                Downloader::new(&download::base_url(base_url.as_deref()), &session, &contact)
+                    .submit(&mut log, year.year, *day, part, &answer)
+            });
+            match submitted {
+                Ok(outcome) => {
+                    println!("{}", outcome);
+                    if outcome == Outcome::Correct {
+                        ExitCode::SUCCESS
+                    } else {
+                        ExitCode::FAILURE
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Encrypt { year, data_dir } | Command::Decrypt { year, data_dir } => {
+            let encrypt = matches!(command, Command::Encrypt { .. });
+            if year.is_some_and(|year| find_year(years, year).is_none()) {
+                return ExitCode::FAILURE;
+            }
+            let key = match crypt::key() {
+                Ok(key) => key,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let mut failed = false;
+            for year in years
+                .iter()
+                .filter(|y| year.is_none_or(|year| y.year == year))
+            {
+                let dir = year_data_dir(year, data_dir.as_deref());
+                let files = match crypt::inputs(&dir, !encrypt) {
+                    Ok(files) => files,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        failed = true;
+                        continue;
+                    }
+                };
+                for file in files {
+                    let done = match encrypt {
+                        true => crypt::encrypt_file(&file, &key),
+                        false => crypt::decrypt_file(&file, &key),
+                    };
+                    match done {
+                        Ok(to) => println!("{} -> {}", file.display(), to.display()),
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            failed = true;
+                        }
+                    }
+                }
+            }
+            if failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Readme {
+            path,
+            answers,
+            runs,
+            data_dir,
+        } => match readme::update(path, years, *answers, *runs, data_dir.as_deref()) {
+            Ok(()) => {
+                println!("Updated {}", path.display());
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
+
+/// One input of one day, solved for every selected part.
+struct Job<'a> {
+    year: &'a Year,
+    solver: &'a dyn Solver,
+    source: Source<'a>,
+    answers: &'a Answers,
+    examples: &'a Examples,
+}
+
+/// A part `run_job` solved, waiting to be printed in order.
+struct Solved {
+    part: Part,
+    result: std::result::Result<Answer, Error>,
+    status: Status,
+    /// Load and parse times, only on the first part of each input.
+    load: Option<Duration>,
+    parse: Option<Duration>,
+    solve: Timing,
+    allocations: Allocations,
+}
+
+/// Loads, parses and solves one input, and draws it with `painter`. Also
+/// returns the CPU time that took on its thread, which `main` sums up over
+/// all jobs, `None` where it can't be measured.
+fn run_job(
+    job: &Job,
+    parts: &[Part],
+    options: &BenchOptions,
+    painter: Option<&Painter>,
+) -> (Vec<Solved>, Option<String>, Option<Duration>) {
+    let start = bench::thread_cpu_time();
+    let solver = job.solver;
+    let example = job.source.example();
+    let (input, load, _) = timed(
+        options,
+        || job.source.load().map_err(|e| e.for_day(solver.day())),
+        std::result::Result::is_ok,
+    );
+    // Parse once and share it between both parts
+    let (parsed, parse) = match &input {
+        Ok(input) => {
+            let (parsed, parse, _) =
+                timed(options, || solver.parse(input), std::result::Result::is_ok);
+            (parsed, Some(parse))
+        }
+        Err(e) => (Err(e.clone()), None),
+    };
+    // Load and parse times go on the first row of the input
+    let mut prepare = Some((load, parse));
+    let mut solved = Vec::new();
+    for &part in parts {
+        // Examples often only cover one of the parts
+        if example.is_some_and(|e| job.examples.expected(e, part).is_none()) {
+            continue;
+        }
+        let (result, solve, allocations) = timed(
+            options,
+            || match &parsed {
+                Ok(parsed) => solver.solve(parsed.as_ref(), part),
+                Err(e) => Some(Err(e.clone())),
+            },
+            |r| matches!(r, Some(Ok(_))),
+        );
+        let Some(result) = result else {
+            continue;
+        };
+        let status = match example {
+            Some(example) => job.examples.status(example, part, result.as_ref().ok()),
+            None => job.answers.status(solver.day(), part, result.as_ref().ok()),
+        };
+        let (load, parse) = match prepare.take() {
+            Some((load, parse)) => (Some(load.per_iteration), parse.map(|p| p.per_iteration)),
+            None => (None, None),
+        };
+        solved.push(Solved {
+            part,
+            result,
+            status,
+            load,
+            parse,
+            solve,
+            allocations,
+        });
+    }
+    let picture = match (&parsed, painter) {
+        (Ok(parsed), Some(painter)) => solver.visualize(parsed.as_ref(), painter),
+        _ => None,
+    };
+    let cpu = start
+        .zip(bench::thread_cpu_time())
+        .map(|(start, end)| end - start);
+    (solved, picture, cpu)
+}
+
+/// Entry point for the `aoc` binary: parses the command line and prints the
+/// results table for the selected years, days and parts.
+pub fn main(years: &[Year]) -> ExitCode {
+    let args = Args::parse();
+    if let Some(command) = &args.command {
+        return run_command(years, command);
+    }
+    if args.watch {
+        return watch::watch(years, &args);
+    }
+    run(years, &args)
+}
+
+/// Solves and prints everything `args` selects.
+fn run(years: &[Year], args: &Args) -> ExitCode {
+    let check = args.check || args.example;
+    let parts: Vec<Part> = Part::iter()
+        .filter(|p| args.part.is_none_or(|part| *p == part))
+        .collect();
+
+    let baseline = match &args.compare {
+        Some(name) => match History::load(&args.history).and_then(|h| h.baseline(name)) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                eprintln!("{}: {}", args.history.display(), e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+
+    let output = Output {
+        format: args.format,
+        example: args.example,
+        status: check,
+        bench: args.bench.bench,
+        allocations: memory::ENABLED,
+    };
+    if let Some(header) = output.header() {
+        println!("{}", header);
+    }
+
+    let input = match args.input.as_deref().map(Source::from_arg).transpose() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut selected = Vec::new();
+    for year in years
+        .iter()
+        .filter(|y| args.year.is_none_or(|year| y.year == year))
+    {
+        let answers = if args.check {
+            Answers::parse(year.answers).map_err(|e| format!("{}/answers.txt: {}", year.year, e))
+        } else {
+            Ok(Answers::default())
+        };
+        let examples =
+            Examples::new(year).map_err(|e| format!("{}/examples/answers.txt: {}", year.year, e));
+        let (answers, examples) = match (answers, examples) {
+            (Ok(answers), Ok(examples)) => (answers, examples),
+            (Err(e), _) | (_, Err(e)) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        let solvers: Vec<Box<dyn Solver>> = (year.solutions)()
+            .into_iter()
+            .filter(|s| args.day.is_none_or(|day| s.day() == day))
+            .collect();
+        selected.push((year, answers, examples, solvers));
+    }
+
+    // answers.txt only holds the answers of the puzzle inputs
+    let unchecked = Answers::default();
+    let mut jobs = Vec::new();
+    for (year, answers, examples, solvers) in selected.iter() {
+        let answers = if input.is_some() { &unchecked } else { answers };
+        for solver in solvers.iter() {
+            let solver = solver.as_ref();
+            let sources: Vec<Source> = if args.example {
+                examples
+                    .for_day(solver.day())
+                    .map(Source::Example)
+                    .collect()
+            } else if let Some(input) = &input {
+                vec![input.clone()]
+            } else {
+                vec![Source::day(year, solver.day(), args.data_dir.as_deref())]
+            };
+            jobs.extend(sources.into_iter().map(|source| Job {
+                year,
+                solver,
+                source,
+                answers,
+                examples,
+            }));
+        }
+    }
+
+    // Benchmarks stay serial so they don't compete for cores
+    let threads = match args.jobs {
+        _ if args.bench.bench => 1,
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let run = args.bench.bench.then(Run::now);
+    // Only a table has room for pictures between its rows
+    let picture_to_stderr = args.format != Format::Table;
+    let painter = args.visualize.then(|| match picture_to_stderr {
+        true => Painter::detect_on(std::io::stderr()),
+        false => Painter::detect(),
+    });
+    let mut recorded: Vec<Entry> = Vec::new();
+
+    let mut regressions = 0;
+    let mut slowdowns = 0;
+    let mut cpu = Some(Duration::ZERO);
+    let mut last_day = None;
+    let mut drawn = false;
+    let wall = Instant::now();
+    jobs::run_ordered(
+        &jobs,
+        threads,
+        |job| run_job(job, &parts, &args.bench, painter.as_ref()),
+        |job, (solved, picture, job_cpu)| {
+            cpu = cpu.zip(job_cpu).map(|(cpu, job_cpu)| cpu + job_cpu);
+            let (year, day) = (job.year.year, job.solver.day());
+            let example = job.source.example();
+            for solved in solved {
+                if last_day != Some((year, day)) {
+                    if let Some(separator) = output.separator() {
+                        println!("{}", separator);
+                    }
+                    last_day = Some((year, day));
+                }
+                if matches!(solved.status, Status::Wrong(_)) {
+                    regressions += 1;
+                }
+                // Only the puzzle inputs are comparable between runs
+                let comparison = match &run {
+                    Some(run)
+                        if solved.result.is_ok() && example.is_none() && args.input.is_none() =>
+                    {
+                        let entry = Entry::new(run, year, day, solved.part, &solved.solve);
+                        let comparison = baseline
+                            .as_ref()
+                            .and_then(|b| b.compare(&entry, args.threshold / 100.0));
+                        recorded.push(entry);
+                        comparison
+                    }
+                    _ => None,
+                };
+                if comparison.is_some_and(|c| c.regression == Some(true)) {
+                    slowdowns += 1;
+                }
+                println!(
+                    "{}",
+                    output.row(&Row {
+                        year,
+                        day,
+                        part: solved.part,
+                        example: example.map(|e| e.name),
+                        result: &solved.result,
+                        status: &solved.status,
+                        load: solved.load,
+                        parse: solved.parse,
+                        solve: solved.solve,
+                        allocations: memory::ENABLED.then_some(solved.allocations),
+                        comparison,
+                    })
+                );
+            }
+            if let Some(picture) = picture {
+                match picture_to_stderr {
+                    true => eprintln!("\n{}\n", picture),
+                    false => println!("\n{}\n", picture),
+                }
+                drawn = true;
+            }
+        },
+    );
+    if args.visualize && !drawn {
+        eprintln!("Nothing to visualize for the selected days");
+    }
+    if let Some(footer) = output.footer(wall.elapsed(), cpu) {
+        println!("{}", footer);
+    }
+
+    if !recorded.is_empty() {
+        if let Err(e) = History::append(&args.history, &recorded) {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+    if regressions > 0 {
+        eprintln!(
+            "{} answer(s) did not match the expected answers",
+            regressions
+        );
+    }
+    if let (Some(baseline), true) = (&baseline, slowdowns > 0) {
+        eprintln!(
+            "{} part(s) were slower than baseline {}",
+            slowdowns, baseline.name
+        );
+    }
+    if regressions > 0 || slowdowns > 0 {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,123 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Whether the `count-allocations` feature installed `Counting` as the
+/// global allocator, so that `count` sees every allocation.
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+// Per thread, so parts solved at once with --jobs don't count each other's
+// allocations. Const initialized, so reading them never allocates.
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static ALLOCATED: Cell<u64> = const { Cell::new(0) };
+    /// Signed, a thread may free memory another thread allocated.
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+fn allocated(bytes: usize) {
+    // try_with, the thread locals may be gone while a thread exits
+    let _ = ALLOCATIONS.try_with(|c| c.set(c.get() + 1));
+    let _ = ALLOCATED.try_with(|c| c.set(c.get() + bytes as u64));
+    resized(bytes as i64);
+}
+
+fn resized(change: i64) {
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + change);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+/// The system allocator, counting what the current thread allocates.
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        resized(-(layout.size() as i64));
+    }
+
+    /// Counted as a new allocation of `new_size` bytes that frees the old one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            allocated(new_size);
+            resized(-(layout.size() as i64));
+        }
+        new
+    }
+}
+
+/// What `f` allocated on this thread while `count` ran it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Allocations {
+    /// Number of allocations, reallocations included.
+    pub count: u64,
+    /// Bytes requested over all of them.
+    pub bytes: u64,
+    /// Most bytes live at once, beyond what was live before.
+    pub peak: u64,
+}
+
+/// Runs `f` and counts its allocations. Only allocations that go through
+/// `Counting` are seen, i.e. none unless `ENABLED`.
+pub fn count<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    let allocations = ALLOCATIONS.with(Cell::get);
+    let bytes = ALLOCATED.with(Cell::get);
+    let live = LIVE.with(Cell::get);
+    PEAK.with(|peak| peak.set(live));
+    let result = f();
+    let counted = Allocations {
+        count: ALLOCATIONS.with(Cell::get) - allocations,
+        bytes: ALLOCATED.with(Cell::get) - bytes,
+        peak: (PEAK.with(Cell::get) - live).max(0) as u64,
+    };
+    (result, counted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let small = Layout::from_size_align(100, 8).unwrap();
+        let large = Layout::from_size_align(1000, 8).unwrap();
+        let ((), counted) = count(|| unsafe {
+            let a = Counting.alloc(small);
+            let a = Counting.realloc(a, small, 300);
+            Counting.dealloc(a, Layout::from_size_align(300, 8).unwrap());
+            let b = Counting.alloc_zeroed(large);
+            Counting.dealloc(b, large);
+        });
+        assert_eq!(
+            counted,
+            Allocations {
+                count: 3,
+                bytes: 1400,
+                peak: 1000,
+            }
+        );
+    }
+}
@@ -0,0 +1,536 @@
+use crate::answer::Answer;
+use crate::answers::Status;
+use crate::bench::Timing;
+use crate::error::Error;
+use crate::history::Comparison;
+use crate::memory::Allocations;
+use crate::solution::Part;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// How `main` prints its results, see `--format`.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq)]
+pub enum Format {
+    /// Aligned columns for the terminal
+    #[default]
+    Table,
+    /// One JSON object per line
+    Json,
+    Csv,
+    Markdown,
+}
+
+/// One solved part, as printed by `Output::row`.
+pub struct Row<'a> {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    pub example: Option<&'a str>,
+    pub result: &'a Result<Answer, Error>,
+    pub status: &'a Status,
+    /// Load and parse times, only on the first row of each input.
+    pub load: Option<Duration>,
+    pub parse: Option<Duration>,
+    pub solve: Timing,
+    /// What solving allocated, with the `count-allocations` feature.
+    pub allocations: Option<Allocations>,
+    /// Against the `--compare` baseline, when it has this part.
+    pub comparison: Option<Comparison>,
+}
+
+/// Prints rows in one `Format`, with the optional columns `main` enabled.
+pub struct Output {
+    pub format: Format,
+    pub example: bool,
+    pub status: bool,
+    pub bench: bool,
+    /// Allocations, bytes and peak bytes columns.
+    pub allocations: bool,
+}
+
+/// Wide enough for any `u64` answer, so rows stay aligned.
+const RESULT_WIDTH: usize = 22;
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:7.2} μs", duration.as_secs_f64() * 1e6)
+}
+
+fn format_timing(timing: &Timing) -> String {
+    match timing.r2 {
+        Some(r2) => format!(
+            "{} (R² = {:4.3})",
+            format_duration(timing.per_iteration),
+            r2
+        ),
+        None => format!(
+            "{} (n = {})",
+            format_duration(timing.per_iteration),
+            timing.iterations
+        ),
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1048575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1048576.0),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn nanos(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1e9
+}
+
+impl Output {
+    /// The column titles, printed once before any row.
+    pub fn header(&self) -> Option<String> {
+        match self.format {
+            Format::Table => {
+                let mut header = format!("|{:^6}|{:^5}|{:^6}|", "Year", "Day", "Part");
+                if self.example {
+                    header += &format!("{:^14}|", "Example");
+                }
+                header += &format!("{:^w$}|", "Result", w = RESULT_WIDTH);
+                if self.status {
+                    header += &format!("{:^9}|", "Status");
+                }
+                header += &format!("{:^12}|{:^12}|", "Load", "Parse");
+                if self.allocations {
+                    header += &format!("{:^8}|{:^11}|{:^11}|", "Allocs", "Bytes", "Peak");
+                }
+                header += &format!(" {:10}", "Solve");
+                Some(header)
+            }
+            Format::Json => None,
+            Format::Csv => {
+                let mut header = "year,day,part,example,answer,status,expected,load_ns,parse_ns,\
+                                  solve_ns,r2,iterations,baseline_ns,change,regression"
+                    .to_string();
+                if self.allocations {
+                    header += ",allocations,allocated_bytes,peak_bytes";
+                }
+                Some(header + ",error")
+            }
+            Format::Markdown => {
+                let mut titles = vec!["Year", "Day", "Part"];
+                if self.example {
+                    titles.push("Example");
+                }
+                titles.push("Result");
+                if self.status {
+                    titles.push("Status");
+                }
+                titles.extend(["Load", "Parse"]);
+                if self.allocations {
+                    titles.extend(["Allocs", "Bytes", "Peak"]);
+                }
+                titles.push("Solve");
+                let align: Vec<&str> = titles.iter().map(|_| "---").collect();
+                Some(format!("| {} |\n|{}|", titles.join(" | "), align.join("|")))
+            }
+        }
+    }
+
+    /// Printed before the first row of every day.
+    pub fn separator(&self) -> Option<String> {
+        if self.format != Format::Table {
+            return None;
+        }
+        let mut line = format!("|{:-<6}|{:-<5}|{:-<6}|", "", "", "");
+        if self.example {
+            line += &format!("{:-<14}|", "");
+        }
+        line += &format!("{:-<w$}|", "", w = RESULT_WIDTH);
+        if self.status {
+            line += &format!("{:-<9}|", "");
+        }
+        line += &format!("{:-<12}|{:-<12}|", "", "");
+        if self.allocations {
+            line += &format!("{:-<8}|{:-<11}|{:-<11}|", "", "", "");
+        }
+        line += &format!("{:-<25}", "");
+        Some(line)
+    }
+
+    /// Total wall-clock time of the run and the CPU time summed over all
+    /// jobs, when that could be measured.
+    pub fn footer(&self, wall: Duration, cpu: Option<Duration>) -> Option<String> {
+        let ms = |d: Duration| d.as_secs_f64() * 1e3;
+        let mut total = format!("Total: {:.2} ms wall", ms(wall));
+        if let Some(cpu) = cpu {
+            write!(total, ", {:.2} ms summed CPU time", ms(cpu)).unwrap();
+        }
+        match self.format {
+            Format::Table => Some(total),
+            Format::Markdown => Some(format!("\n{}", total)),
+            Format::Json | Format::Csv => None,
+        }
+    }
+
+    pub fn row(&self, row: &Row) -> String {
+        match self.format {
+            Format::Table => self.table_row(row),
+            Format::Json => self.json_row(row),
+            Format::Csv => self.csv_row(row),
+            Format::Markdown => self.markdown_row(row),
+        }
+    }
+
+    fn solve_text(&self, row: &Row) -> String {
+        match row.result {
+            Ok(_) if self.bench => {
+                let mut text = format_timing(&row.solve);
+                if let Some(c) = row.comparison {
+                    write!(text, " {:+.1}%", c.change * 100.0).unwrap();
+                }
+                text
+            }
+            Ok(_) => format_duration(row.solve.per_iteration),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    fn table_row(&self, row: &Row) -> String {
+        let mut line = format!("|{:^6}|{:^5}|{:^6}|", row.year, row.day, row.part);
+        if let Some(example) = row.example {
+            line += &format!("{:^14}|", example);
+        }
+        match row.result {
+            Ok(r) => line += &format!("{:^w$}|", r, w = RESULT_WIDTH),
+            Err(_) => line += &format!("{:^w$}|", "error", w = RESULT_WIDTH),
+        }
+        if self.status {
+            line += &format!("{:^9}|", row.status);
+        }
+        let load = row.load.map_or(String::new(), format_duration);
+        let parse = row.parse.map_or(String::new(), format_duration);
+        line += &format!("{:>11} |{:>11} |", load, parse);
+        if self.allocations {
+            let [count, bytes, peak] = self.allocation_cells(row);
+            line += &format!("{:>7} |{:>10} |{:>10} |", count, bytes, peak);
+        }
+        line += &format!(" {}", self.solve_text(row));
+        if let Status::Wrong(expected) = row.status {
+            match row.result {
+                Ok(actual) => write!(line, "\n  expected {}, got {}", expected, actual).unwrap(),
+                Err(_) => write!(line, "\n  expected {}, got an error", expected).unwrap(),
+            }
+        }
+        if let Some(c) = row.comparison.filter(|c| c.regression == Some(true)) {
+            write!(
+                line,
+                "\n  slower than the baseline's {}",
+                format_duration(c.baseline).trim()
+            )
+            .unwrap();
+        }
+        line
+    }
+
+    /// Count, bytes and peak, empty when the part failed.
+    fn allocation_cells(&self, row: &Row) -> [String; 3] {
+        match row.allocations.filter(|_| row.result.is_ok()) {
+            Some(a) => [
+                a.count.to_string(),
+                format_bytes(a.bytes),
+                format_bytes(a.peak),
+            ],
+            None => Default::default(),
+        }
+    }
+
+    fn markdown_row(&self, row: &Row) -> String {
+        let mut cells = vec![
+            row.year.to_string(),
+            row.day.to_string(),
+            row.part.to_string(),
+        ];
+        if let Some(example) = row.example {
+            cells.push(example.to_string());
+        }
+        cells.push(match row.result {
+            Ok(r) => r.to_string(),
+            Err(_) => "error".to_string(),
+        });
+        if self.status {
+            cells.push(row.status.to_string());
+        }
+        cells.push(row.load.map_or(String::new(), format_duration));
+        cells.push(row.parse.map_or(String::new(), format_duration));
+        if self.allocations {
+            cells.extend(self.allocation_cells(row));
+        }
+        cells.push(self.solve_text(row));
+        let cells: Vec<String> = cells.iter().map(|c| c.trim().replace('|', "\\|")).collect();
+        format!("| {} |", cells.join(" | "))
+    }
+
+    fn fields(&self, row: &Row) -> Vec<(&'static str, Option<String>, bool)> {
+        // (name, value, whether the value is a string)
+        let expected = match row.status {
+            Status::Wrong(expected) => Some(expected.to_string()),
+            _ => None,
+        };
+        let solved = row.result.is_ok();
+        let allocations = row.allocations.filter(|_| solved);
+        let mut fields = vec![
+            ("year", Some(row.year.to_string()), false),
+            ("day", Some(row.day.to_string()), false),
+            ("part", Some(row.part.to_string()), false),
+            ("example", row.example.map(str::to_string), true),
+            (
+                "answer",
+                row.result.as_ref().ok().map(Answer::to_string),
+                true,
+            ),
+            ("status", self.status.then(|| row.status.to_string()), true),
+            ("expected", expected, true),
+            ("load_ns", row.load.map(|d| nanos(d).to_string()), false),
+            ("parse_ns", row.parse.map(|d| nanos(d).to_string()), false),
+            (
+                "solve_ns",
+                solved.then(|| nanos(row.solve.per_iteration).to_string()),
+                false,
+            ),
+            (
+                "r2",
+                row.solve
+                    .r2
+                    .filter(|r2| r2.is_finite())
+                    .map(|r2| r2.to_string()),
+                false,
+            ),
+            (
+                "iterations",
+                solved.then(|| row.solve.iterations.to_string()),
+                false,
+            ),
+            (
+                "baseline_ns",
+                row.comparison.map(|c| nanos(c.baseline).to_string()),
+                false,
+            ),
+            (
+                "change",
+                row.comparison.map(|c| c.change.to_string()),
+                false,
+            ),
+            (
+                "regression",
+                row.comparison
+                    .and_then(|c| c.regression)
+                    .map(|r| r.to_string()),
+                false,
+            ),
+        ];
+        if self.allocations {
+            fields.extend([
+                (
+                    "allocations",
+                    allocations.map(|a| a.count.to_string()),
+                    false,
+                ),
+                (
+                    "allocated_bytes",
+                    allocations.map(|a| a.bytes.to_string()),
+                    false,
+                ),
+                ("peak_bytes", allocations.map(|a| a.peak.to_string()), false),
+            ]);
+        }
+        fields.push((
+            "error",
+            row.result.as_ref().err().map(Error::to_string),
+            true,
+        ));
+        fields
+    }
+
+    fn json_row(&self, row: &Row) -> String {
+        let fields: Vec<String> = self
+            .fields(row)
+            .into_iter()
+            .map(|(name, value, string)| {
+                let value = match value {
+                    Some(v) if string => json_string(&v),
+                    Some(v) => v,
+                    None => "null".to_string(),
+                };
+                format!("{}:{}", json_string(name), value)
+            })
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+
+    fn csv_row(&self, row: &Row) -> String {
+        let fields: Vec<String> = self
+            .fields(row)
+            .into_iter()
+            .map(|(_, value, _)| csv_field(&value.unwrap_or_default()))
+            .collect();
+        fields.join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(format: Format) -> Output {
+        Output {
+            format,
+            example: false,
+            status: true,
+            bench: false,
+            allocations: false,
+        }
+    }
+
+    fn timing() -> Timing {
+        Timing {
+            iterations: 1,
+            per_iteration: Duration::from_micros(2),
+            r2: None,
+            std_error: None,
+        }
+    }
+
+    #[test]
+    fn test_json_row() {
+        let result = Ok(Answer::from(42u32));
+        let status = Status::Wrong(Answer::from(41u32));
+        let row = Row {
+            year: 2023,
+            day: 1,
+            part: Part::Part2,
+            example: None,
+            result: &result,
+            status: &status,
+            load: None,
+            parse: Some(Duration::from_micros(1)),
+            solve: timing(),
+            allocations: None,
+            comparison: None,
+        };
+        assert_eq!(
+            output(Format::Json).row(&row),
+            "{\"year\":2023,\"day\":1,\"part\":2,\"example\":null,\"answer\":\"42\",\
+             \"status\":\"wrong\",\"expected\":\"41\",\"load_ns\":null,\"parse_ns\":1000,\
+             \"solve_ns\":2000,\"r2\":null,\"iterations\":1,\"baseline_ns\":null,\
+             \"change\":null,\"regression\":null,\"error\":null}"
+        );
+    }
+
+    #[test]
+    fn test_csv_row_quotes_errors() {
+        let result = Err(Error::new("Bad, \"really\""));
+        let row = Row {
+            year: 2023,
+            day: 1,
+            part: Part::Part1,
+            example: Some("day1"),
+            result: &result,
+            status: &Status::Unknown,
+            load: None,
+            parse: None,
+            solve: timing(),
+            allocations: None,
+            comparison: None,
+        };
+        assert_eq!(
+            output(Format::Csv).row(&row),
+            "2023,1,1,day1,,unknown,,,,,,,,,,\"Bad, \"\"really\"\"\""
+        );
+    }
+
+    #[test]
+    fn test_allocation_columns() {
+        let result = Ok(Answer::from(42u32));
+        let output = Output {
+            allocations: true,
+            ..output(Format::Csv)
+        };
+        let row = Row {
+            year: 2023,
+            day: 8,
+            part: Part::Part1,
+            example: None,
+            result: &result,
+            status: &Status::Correct,
+            load: None,
+            parse: None,
+            solve: timing(),
+            allocations: Some(Allocations {
+                count: 3,
+                bytes: 4096,
+                peak: 2048,
+            }),
+            comparison: None,
+        };
+        assert!(output.header().unwrap().ends_with(",peak_bytes,error"));
+        assert_eq!(
+            output.row(&row),
+            "2023,8,1,,42,correct,,,,2000,,1,,,,3,4096,2048,"
+        );
+        let table = Output {
+            format: Format::Table,
+            ..output
+        };
+        assert!(table
+            .row(&row)
+            .contains("|      3 |   4.0 KiB |   2.0 KiB |"));
+    }
+
+    #[test]
+    fn test_table_fits_u64_answers() {
+        let table = output(Format::Table);
+        let result = Ok(Answer::from(u64::MAX));
+        let row = Row {
+            year: 2023,
+            day: 8,
+            part: Part::Part2,
+            example: None,
+            result: &result,
+            status: &Status::Unknown,
+            load: None,
+            parse: None,
+            solve: timing(),
+            allocations: None,
+            comparison: None,
+        };
+        // The column borders of the row line up with the header's
+        let borders = |line: &str| -> Vec<usize> {
+            line.char_indices()
+                .filter(|&(_, c)| c == '|')
+                .map(|(i, _)| i)
+                .collect()
+        };
+        assert_eq!(borders(&table.row(&row)), borders(&table.header().unwrap()));
+    }
+}
@@ -0,0 +1,325 @@
+use crate::answer::Answer;
+use crate::answers::{Answers, Status};
+use crate::bench;
+use crate::error::Error;
+use crate::solution::Part;
+use crate::source::Source;
+use crate::Year;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use strum::IntoEnumIterator;
+
+/// The generated region of the README lies between these lines.
+pub const START: &str = "<!-- aoc readme start -->";
+pub const END: &str = "<!-- aoc readme end -->";
+
+/// How `aoc readme` shows the answers.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq)]
+pub enum AnswerStyle {
+    /// Only stars and timings
+    #[default]
+    Hidden,
+    /// A short hash, enough to tell whether an answer changed. Not a way to
+    /// hide them: an unsalted hash of a number is undone by trying numbers
+    Hashed,
+    Shown,
+}
+
+/// One part of a day, solved `runs` times.
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: Part,
+    pub result: Result<Answer, Error>,
+    pub status: Status,
+    /// Median solve time.
+    pub median: Duration,
+}
+
+/// A day's row of the README table.
+#[derive(Debug)]
+pub struct DayResult {
+    pub day: u32,
+    /// Median parse time, `None` if the input could not be loaded or parsed.
+    pub parse: Option<Duration>,
+    /// Why there is nothing to solve.
+    pub error: Option<Error>,
+    pub parts: Vec<PartResult>,
+}
+
+impl DayResult {
+    /// A star for every answer that matches `answers.txt`.
+    pub fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|p| p.status == Status::Correct)
+            .count()
+    }
+}
+
+fn median(mut times: Vec<Duration>) -> Duration {
+    times.sort();
+    times.get(times.len() / 2).copied().unwrap_or_default()
+}
+
+/// Runs `f` `runs` times, returning the first result and the median time.
+fn repeat<T>(runs: usize, mut f: impl FnMut() -> T) -> (T, Duration) {
+    let (result, first) = bench::once(&mut f);
+    let mut times = vec![first];
+    for _ in 1..runs {
+        times.push(bench::once(&mut f).1);
+    }
+    (result, median(times))
+}
+
+/// Solves every day of `year` on its puzzle input, `runs` times each.
+pub fn solve_year(
+    year: &Year,
+    runs: usize,
+    data_dir: Option<&Path>,
+) -> Result<Vec<DayResult>, Error> {
+    let answers: Answers = Answers::parse(year.answers)
+        .map_err(|e| Error::new(format!("{}/answers.txt: {}", year.year, e)))?;
+    let mut days = Vec::new();
+    for solver in (year.solutions)() {
+        let day = solver.day();
+        let parsed = Source::day(year, day, data_dir)
+            .load()
+            .map(|input| repeat(runs, || solver.parse(&input)));
+        let (parsed, parse) = match parsed {
+            Ok((Ok(parsed), parse)) => (parsed, parse),
+            Ok((Err(e), _)) | Err(e) => {
+                days.push(DayResult {
+                    day,
+                    parse: None,
+                    error: Some(e),
+                    parts: Vec::new(),
+                });
+                continue;
+            }
+        };
+        let mut parts = Vec::new();
+        for part in Part::iter() {
+            let (result, median) = repeat(runs, || solver.solve(parsed.as_ref(), part));
+            let Some(result) = result else {
+                continue;
+            };
+            let status = answers.status(day, part, result.as_ref().ok());
+            parts.push(PartResult {
+                part,
+                result,
+                status,
+                median,
+            });
+        }
+        days.push(DayResult {
+            day,
+            parse: Some(parse),
+            error: None,
+            parts,
+        });
+    }
+    Ok(days)
+}
+
+/// FNV-1a, stable across Rust versions unlike `DefaultHasher`. Only tells
+/// answers apart, anyone can find the number behind it by trying them.
+fn hash(answer: &str) -> String {
+    let hash = answer.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:08x}", hash >> 32)
+}
+
+fn human(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs < 1e-3 {
+        format!("{:.1} μs", secs * 1e6)
+    } else if secs < 1.0 {
+        format!("{:.2} ms", secs * 1e3)
+    } else {
+        format!("{:.2} s", secs)
+    }
+}
+
+fn part_cell(part: &PartResult, style: AnswerStyle) -> String {
+    let answer = match &part.result {
+        Err(_) => return "error".to_string(),
+        Ok(answer) => answer.to_string(),
+    };
+    let star = match part.status {
+        Status::Correct => " ⭐",
+        Status::Wrong(_) => " ❌",
+        Status::Unknown => "",
+    };
+    match style {
+        AnswerStyle::Hidden => format!("{}{}", human(part.median), star),
+        AnswerStyle::Hashed => format!("`{}` {}{}", hash(&answer), human(part.median), star),
+        AnswerStyle::Shown => format!("`{}` {}{}", answer, human(part.median), star),
+    }
+}
+
+/// The generated region: a table per year with stars, answers and median
+/// timings.
+pub fn render(years: &[(u32, Vec<DayResult>)], style: AnswerStyle, runs: usize) -> String {
+    let mut out = String::new();
+    for (year, days) in years {
+        let stars: usize = days.iter().map(DayResult::stars).sum();
+        let total: Duration = days
+            .iter()
+            .flat_map(|d| d.parse.into_iter().chain(d.parts.iter().map(|p| p.median)))
+            .sum();
+        writeln!(out, "### {}\n", year).unwrap();
+        writeln!(out, "{} ⭐, {} in total\n", stars, human(total)).unwrap();
+        writeln!(out, "| Day | Stars | Parse | Part 1 | Part 2 |").unwrap();
+        writeln!(out, "|---:|---|---:|---:|---:|").unwrap();
+        for day in days {
+            let mut cells = vec![
+                day.day.to_string(),
+                "⭐".repeat(day.stars()),
+                day.parse.map_or(String::new(), human),
+            ];
+            match &day.error {
+                Some(e) => cells.push(e.to_string().replace('|', "\\|")),
+                None => {
+                    for part in Part::iter() {
+                        cells.push(
+                            day.parts
+                                .iter()
+                                .find(|p| p.part == part)
+                                .map_or(String::new(), |p| part_cell(p, style)),
+                        );
+                    }
+                }
+            }
+            writeln!(out, "| {} |", cells.join(" | ")).unwrap();
+        }
+        out.push('\n');
+    }
+    writeln!(
+        out,
+        "Median of {} runs on puzzle inputs, generated by `aoc readme`.",
+        runs
+    )
+    .unwrap();
+    out
+}
+
+/// Replaces whatever is between `START` and `END` with `region`, appending
+/// both markers when the text has none.
+pub fn replace_region(text: &str, region: &str) -> Result<String, Error> {
+    let block = format!("{}\n{}{}\n", START, region, END);
+    match (text.find(START), text.find(END)) {
+        (None, None) => {
+            let separator = if text.is_empty() || text.ends_with("\n\n") {
+                ""
+            } else if text.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            Ok(format!("{}{}{}", text, separator, block))
+        }
+        (Some(start), Some(end)) if start < end => {
+            let rest = &text[end + END.len()..];
+            Ok(format!(
+                "{}{}{}",
+                &text[..start],
+                block,
+                rest.strip_prefix('\n').unwrap_or(rest)
+            ))
+        }
+        _ => Err(Error::new(format!(
+            "Expected {} before {} on their own lines",
+            START, END
+        ))),
+    }
+}
+
+/// `aoc readme`: solves every year and rewrites the region of `path`.
+pub fn update(
+    path: &Path,
+    years: &[Year],
+    style: AnswerStyle,
+    runs: usize,
+    data_dir: Option<&Path>,
+) -> Result<(), Error> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(Error::new(format!(
+                "Could not read {}: {}",
+                path.display(),
+                e
+            )))
+        }
+    };
+    // Fail on bad markers before spending time on the solutions
+    replace_region(&text, "")?;
+    let mut results = Vec::new();
+    for year in years {
+        results.push((year.year, solve_year(year, runs.max(1), data_dir)?));
+    }
+    let text = replace_region(&text, &render(&results, style, runs.max(1)))?;
+    fs::write(path, text)
+        .map_err(|e| Error::new(format!("Could not write {}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u32, answer: u32, status: Status) -> DayResult {
+        DayResult {
+            day,
+            parse: Some(Duration::from_micros(20)),
+            error: None,
+            parts: vec![PartResult {
+                part: Part::Part1,
+                result: Ok(Answer::from(answer)),
+                status,
+                median: Duration::from_micros(1500),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_replace_region() {
+        let readme = "# aoc\n";
+        let once = replace_region(readme, "one\n").unwrap();
+        assert_eq!(once, format!("# aoc\n\n{}\none\n{}\n", START, END));
+        let twice = replace_region(&format!("{}more\n", once), "two\n").unwrap();
+        assert_eq!(twice, format!("# aoc\n\n{}\ntwo\n{}\nmore\n", START, END));
+        assert!(replace_region(&format!("{}\n{}\n", END, START), "").is_err());
+        assert!(replace_region(START, "").is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let years = vec![(
+            2023,
+            vec![
+                day(1, 142, Status::Correct),
+                day(2, 8, Status::Unknown),
+                DayResult {
+                    day: 3,
+                    parse: None,
+                    error: Some(Error::new("Could not read day3.txt")),
+                    parts: Vec::new(),
+                },
+            ],
+        )];
+        let hidden = render(&years, AnswerStyle::Hidden, 5);
+        assert!(hidden.contains("1 ⭐, 3.04 ms in total"));
+        assert!(hidden.contains("| 1 | ⭐ | 20.0 μs | 1.50 ms ⭐ |  |\n"));
+        assert!(hidden.contains("| 3 |  |  | Could not read day3.txt |\n"));
+        assert!(!hidden.contains("142"));
+        let shown = render(&years, AnswerStyle::Shown, 5);
+        assert!(shown.contains("`142` 1.50 ms ⭐"));
+        let hashed = render(&years, AnswerStyle::Hashed, 5);
+        assert!(hashed.contains(&format!("`{}` 1.50 ms", hash("142"))));
+        assert!(!hashed.contains("142`"));
+    }
+}
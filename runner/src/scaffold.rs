@@ -0,0 +1,161 @@
+use crate::crypt;
+use crate::error::Error;
+use crate::source::year_data_dir;
+use crate::Year;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A new day module; `DAY` is replaced by the day number.
+const TEMPLATE: &str = r#"use aoc_runner::{Answer, Error, Input, Solution};
+
+fn parse_line(line: &str) -> Result<String, Error> {
+    Ok(line.to_string())
+}
+
+pub fn solve_part1(lines: &[String]) -> usize {
+    lines.len()
+}
+
+pub fn solve_part2(_lines: &[String]) -> usize {
+    0
+}
+
+pub struct DayDAY;
+
+impl Solution for DayDAY {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, Error> {
+        aoc_runner::parse_lines(input.lines(), parse_line)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error> {
+        Ok(solve_part1(parsed).into())
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Option<Result<Answer, Error>> {
+        Some(Ok(solve_part2(parsed).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_example() {
+        let input = Input::new(include_str!("../examples/dayDAY.txt"));
+        assert!(DayDAY::parse(&input).is_ok());
+    }
+}
+"#;
+
+/// The year crate's directory, the parent of its default `data/`.
+pub(crate) fn crate_dir(year: &Year) -> Result<PathBuf, Error> {
+    Path::new(year.data_dir)
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| Error::new(format!("No crate directory for {}", year.year)))
+}
+
+/// `aoc new`: writes `src/dayN.rs`, an empty `examples/dayN.txt` and an
+/// empty `dayN.txt` in the year's data directory, unless the input is there
+/// encrypted already. The year's `build.rs` registers the module on the next
+/// build. Nothing is written if any of them exist.
+pub fn new_day(year: &Year, day: u32, data_dir: Option<&Path>) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::new(format!("Day {} is not between 1 and 25", day)));
+    }
+    let root = crate_dir(year)?;
+    let mut files = vec![
+        (
+            root.join("src").join(format!("day{}.rs", day)),
+            TEMPLATE.replace("DAY", &day.to_string()),
+        ),
+        (
+            root.join("examples").join(format!("day{}.txt", day)),
+            String::new(),
+        ),
+    ];
+    let input = year_data_dir(year, data_dir).join(format!("day{}.txt", day));
+    if !crypt::encrypted_path(&input).exists() {
+        files.push((input, String::new()));
+    }
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(Error::new(format!("{} already exists", path.display())));
+    }
+    for (path, contents) in files.iter() {
+        let err =
+            |e: std::io::Error| Error::new(format!("Could not write {}: {}", path.display(), e));
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(err)?;
+        }
+        // create_new so a file that appeared meanwhile is never overwritten
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(contents.as_bytes()))
+            .map_err(err)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp::TempDir;
+    use std::fs;
+
+    fn year(data_dir: &'static str) -> Year {
+        Year {
+            year: 2023,
+            solutions: Vec::new,
+            data_dir,
+            answers: "",
+            examples: &[],
+            example_answers: "",
+        }
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = TempDir::new("scaffold");
+        let data_dir: &'static str = Box::leak(
+            root.join("data")
+                .to_string_lossy()
+                .into_owned()
+                .into_boxed_str(),
+        );
+        let year = year(data_dir);
+
+        let files = new_day(&year, 9, None).unwrap();
+        assert_eq!(files.len(), 3);
+        let module = fs::read_to_string(root.join("src/day9.rs")).unwrap();
+        assert!(module.contains("pub struct Day9;"));
+        assert!(module.contains("\"../examples/day9.txt\""));
+        assert!(root.join("data/day9.txt").exists());
+
+        // Refuses to overwrite, even when only some of the files exist
+        fs::remove_file(root.join("src/day9.rs")).unwrap();
+        fs::write(root.join("data/day9.txt"), "input").unwrap();
+        assert!(new_day(&year, 9, None).is_err());
+        assert_eq!(
+            fs::read_to_string(root.join("data/day9.txt")).unwrap(),
+            "input"
+        );
+        assert!(!root.join("src/day9.rs").exists());
+
+        assert!(new_day(&year, 26, None).is_err());
+
+        // The stub goes to --data-dir, and not next to an encrypted input
+        let elsewhere = root.join("elsewhere");
+        let files = new_day(&year, 10, Some(&elsewhere)).unwrap();
+        assert_eq!(files[2], elsewhere.join("2023/day10.txt"));
+        fs::write(root.join("data/day11.txt.enc"), "").unwrap();
+        let files = new_day(&year, 11, None).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(!root.join("data/day11.txt").exists());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::input::Input;
+use crate::visualize::Painter;
+use std::any::Any;
+use std::fmt;
+use std::marker::PhantomData;
+use strum_macros::EnumIter;
+
+#[derive(Debug, Clone, Copy, EnumIter, PartialEq)]
+pub enum Part {
+    Part1 = 1,
+    Part2 = 2,
+}
+
+impl PartialEq<u32> for Part {
+    fn eq(&self, other: &u32) -> bool {
+        *other == (*self as u32)
+    }
+}
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (*self as u32).fmt(f)
+    }
+}
+
+/// A single day's puzzle: the input is parsed once and both parts are
+/// solved from the parsed value.
+///
+/// Any `src/dayN.rs` defining `pub struct DayN` that implements this trait
+/// is picked up by `build.rs` and added to the registry.
+pub trait Solution {
+    type Parsed: 'static;
+
+    fn parse(input: &Input) -> Result<Self::Parsed, Error>;
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error>;
+
+    // Days with a single part (e.g. day 25) leave this out.
+    fn part2(_parsed: &Self::Parsed) -> Option<Result<Answer, Error>> {
+        None
+    }
+
+    /// `--visualize`: a picture of the parsed input, for days that draw one.
+    fn visualize(_parsed: &Self::Parsed, _painter: &Painter) -> Option<String> {
+        None
+    }
+}
+
+/// Object safe view of a `Solution` so the runner can iterate over every day.
+pub trait Solver: Send + Sync {
+    fn day(&self) -> u32;
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>, Error>;
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Option<Result<Answer, Error>>;
+    fn visualize(&self, parsed: &dyn Any, painter: &Painter) -> Option<String>;
+}
+
+struct Registered<S> {
+    day: u32,
+    solution: PhantomData<fn() -> S>,
+}
+
+impl<S: Solution> Solver for Registered<S> {
+    fn day(&self) -> u32 {
+        self.day
+    }
+
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>, Error> {
+        match S::parse(input) {
+            Ok(parsed) => Ok(Box::new(parsed)),
+            Err(e) => Err(e.for_day(self.day)),
+        }
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Option<Result<Answer, Error>> {
+        let parsed = parsed
+            .downcast_ref::<S::Parsed>()
+            .expect("parsed input belongs to another day");
+        let result = match part {
+            Part::Part1 => Some(S::part1(parsed)),
+            Part::Part2 => S::part2(parsed),
+        };
+        result.map(|r| r.map_err(|e| e.for_day(self.day)))
+    }
+
+    fn visualize(&self, parsed: &dyn Any, painter: &Painter) -> Option<String> {
+        let parsed = parsed
+            .downcast_ref::<S::Parsed>()
+            .expect("parsed input belongs to another day");
+        S::visualize(parsed, painter)
+    }
+}
+
+pub fn register<S: Solution + 'static>(day: u32) -> Box<dyn Solver> {
+    Box::new(Registered::<S> {
+        day,
+        solution: PhantomData,
+    })
+}
@@ -0,0 +1,93 @@
+use crate::crypt;
+use crate::error::Error;
+use crate::examples::Example;
+use crate::input::Input;
+use crate::Year;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Overrides the data directory for every year; inputs are then read from
+/// `$AOC_DATA_DIR/<year>/dayN.txt`.
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+/// Where a day's puzzle input comes from.
+#[derive(Clone)]
+pub enum Source<'a> {
+    File(PathBuf),
+    /// Standard input, read once up front so it can be solved repeatedly.
+    Stdin(String),
+    Example(&'a Example),
+}
+
+impl<'a> Source<'a> {
+    /// `--input`: a path, or `-` for stdin.
+    pub fn from_arg(arg: &Path) -> Result<Self, Error> {
+        if arg != Path::new("-") {
+            return Ok(Source::File(arg.to_path_buf()));
+        }
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| Error::new(format!("Could not read stdin: {}", e)))?;
+        Ok(Source::Stdin(input))
+    }
+
+    /// The default `dayN.txt` in the year's data directory.
+    pub fn day(year: &Year, day: u32, data_dir: Option<&Path>) -> Self {
+        Source::File(year_data_dir(year, data_dir).join(format!("day{}.txt", day)))
+    }
+
+    pub fn example(&self) -> Option<&'a Example> {
+        match self {
+            Source::Example(example) => Some(example),
+            _ => None,
+        }
+    }
+
+    pub fn load(&self) -> Result<Input, Error> {
+        match self {
+            Source::File(path) => load_file(path),
+            Source::Stdin(input) => Ok(Input::new(input)),
+            Source::Example(example) => Ok(example.input()),
+        }
+    }
+}
+
+/// `data_dir` (from `--data-dir`) or `$AOC_DATA_DIR`, joined with the year,
+/// falling back to the `data/` directory of the year's crate.
+pub fn year_data_dir(year: &Year, data_dir: Option<&Path>) -> PathBuf {
+    match data_dir
+        .map(Path::to_path_buf)
+        .or_else(|| env::var_os(DATA_DIR_VAR).map(PathBuf::from))
+    {
+        Some(dir) => dir.join(year.year.to_string()),
+        None => PathBuf::from(year.data_dir),
+    }
+}
+
+/// Reads an input, decrypting `dayN.txt.enc` when that is given, or when
+/// it exists in place of `dayN.txt`. An empty `dayN.txt`, as `aoc new`
+/// leaves it, does not hide the encrypted input.
+pub fn load_file(path: &Path) -> Result<Input, Error> {
+    load_file_with(path, crypt::key)
+}
+
+/// `load_file`, with `key` asked for the input key only when needed.
+pub(crate) fn load_file_with(
+    path: &Path,
+    key: impl FnOnce() -> Result<String, Error>,
+) -> Result<Input, Error> {
+    let encrypted = crypt::encrypted_path(path);
+    let stub = fs::metadata(path).map_or(true, |m| m.len() == 0);
+    let input = if crypt::is_encrypted(path) {
+        crypt::read_encrypted(path, &key()?)?
+    } else if stub && encrypted.exists() {
+        crypt::read_encrypted(&encrypted, &key()?)?
+    } else {
+        fs::read_to_string(path)
+            .map_err(|e| Error::new(format!("Could not read {}: {}", path.display(), e)))?
+    };
+    Ok(Input::new(&input))
+}
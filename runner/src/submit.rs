@@ -0,0 +1,349 @@
+use crate::answer::Answer;
+use crate::download::Downloader;
+use crate::error::Error;
+use crate::solution::Part;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What the server said about a submitted answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after the last wrong answer, seconds left if known.
+    Wait(Option<u64>),
+    /// The part was already solved, or the previous part wasn't yet.
+    WrongLevel,
+    /// A page we don't recognize, as text.
+    Unknown(String),
+}
+
+impl Outcome {
+    /// The server did not like the answer itself.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+
+    /// Name in the submission log.
+    fn token(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+            Outcome::Wait(_) => "wait",
+            Outcome::WrongLevel => "wrong-level",
+            Outcome::Unknown(_) => "unknown",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        Some(match token {
+            "correct" => Outcome::Correct,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            "wrong" => Outcome::Wrong,
+            "wait" => Outcome::Wait(None),
+            "wrong-level" => Outcome::WrongLevel,
+            "unknown" => Outcome::Unknown(String::new()),
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait(Some(seconds)) => write!(f, "too soon, wait {}s", seconds),
+            Outcome::Wait(None) => write!(f, "too soon"),
+            Outcome::WrongLevel => write!(f, "not the part the puzzle is at"),
+            Outcome::Unknown(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, without tags.
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Seconds in "You have 1m 5s left to wait".
+fn wait_seconds(text: &str) -> Option<u64> {
+    let rest = &text[text.find("You have ")? + "You have ".len()..];
+    let amount = &rest[..rest.find(" left to wait")?];
+    amount.split_whitespace().try_fold(0, |total, part| {
+        let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let number: u64 = number.parse().ok()?;
+        match unit {
+            "h" => Some(total + number * 3600),
+            "m" => Some(total + number * 60),
+            "s" => Some(total + number),
+            _ => None,
+        }
+    })
+}
+
+/// Reads the outcome from the page the server answers a submission with.
+pub fn parse_response(html: &str) -> Outcome {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::Wait(wait_seconds(&text))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown(text.chars().take(200).collect())
+    }
+}
+
+/// One attempt in the submission log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submission {
+    pub timestamp: u64,
+    pub day: u32,
+    pub part: u32,
+    pub outcome: Outcome,
+    pub answer: Answer,
+}
+
+/// Every answer submitted for a year, kept in `submissions.txt` in its data
+/// directory: one `<timestamp> <day> <part> <outcome> <answer>` per line.
+pub struct SubmissionLog {
+    pub path: PathBuf,
+    pub submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Reads the log at `path`; a missing file is an empty log.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(Error::new(format!(
+                    "Could not read {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+        let mut submissions = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.splitn(5, ' ').collect();
+            let [timestamp, day, part, outcome, answer] = fields[..] else {
+                return Err(
+                    Error::new("Expected '<timestamp> <day> <part> <outcome> <answer>'")
+                        .on_line(i + 1, line),
+                );
+            };
+            let invalid =
+                |field: &str| Error::at(line, field, "Invalid field").on_line(i + 1, line);
+            let Ok(answer) = answer.parse::<Answer>();
+            submissions.push(Submission {
+                timestamp: timestamp.parse().map_err(|_| invalid(timestamp))?,
+                day: day.parse().map_err(|_| invalid(day))?,
+                part: part.parse().map_err(|_| invalid(part))?,
+                outcome: Outcome::from_token(outcome).ok_or_else(|| invalid(outcome))?,
+                answer,
+            });
+        }
+        Ok(SubmissionLog {
+            path: path.to_path_buf(),
+            submissions,
+        })
+    }
+
+    /// Why `answer` should not be submitted, judging by earlier attempts.
+    pub fn refuse(&self, day: u32, part: Part, answer: &Answer) -> Option<String> {
+        let earlier = self
+            .submissions
+            .iter()
+            .filter(|s| s.day == day && s.part == part as u32);
+        for s in earlier {
+            match &s.outcome {
+                Outcome::Correct => {
+                    return Some(format!(
+                        "day {} part {} was solved with {}",
+                        day, part, s.answer
+                    ))
+                }
+                o if o.is_wrong() && s.answer == *answer => {
+                    return Some(format!("{} was already submitted: {}", answer, o))
+                }
+                Outcome::TooHigh if answer >= &s.answer => {
+                    return Some(format!("{} was too high already", s.answer))
+                }
+                Outcome::TooLow if answer <= &s.answer => {
+                    return Some(format!("{} was too low already", s.answer))
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    pub fn record(&mut self, submission: Submission) -> Result<(), Error> {
+        let err =
+            |e: io::Error| Error::new(format!("Could not write {}: {}", self.path.display(), e));
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(err)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(err)?;
+        writeln!(
+            file,
+            "{} {} {} {} {}",
+            submission.timestamp,
+            submission.day,
+            submission.part,
+            submission.outcome.token(),
+            submission.answer
+        )
+        .map_err(err)?;
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+impl Downloader {
+    /// POSTs `answer` unless the log rules it out, and logs the outcome.
+    pub fn submit(
+        &mut self,
+        log: &mut SubmissionLog,
+        year: u32,
+        day: u32,
+        part: Part,
+        answer: &Answer,
+    ) -> Result<Outcome, Error> {
+        if let Some(reason) = log.refuse(day, part, answer) {
+            return Err(Error::new(format!("Not submitting, {}", reason)));
+        }
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = (part as u32).to_string();
+        let answer_text = answer.to_string();
+        let outcome = match self.request(&url, &[("level", &level), ("answer", &answer_text)])? {
+            (200, body) => parse_response(&body),
+            (status, body) => {
+                return Err(Error::new(format!(
+                    "HTTP {} from {}: {}",
+                    status,
+                    url,
+                    body.lines().next().unwrap_or("").trim()
+                )))
+            }
+        };
+        log.record(Submission {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            day,
+            part: part as u32,
+            outcome: outcome.clone(),
+            answer: answer.clone(),
+        })?;
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::download::tests::serve;
+    use crate::temp::TempDir;
+
+    const CORRECT: &str = "<main>\n<article><p>That's the right answer! You are <em>one gold star</em> closer to restoring snow operations. <a href=\"/2023/day/1#part2\">[Continue to Part Two]</a></p></article>\n</main>";
+    const TOO_HIGH: &str = "<main>\n<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+    const WAIT: &str = "<main>\n<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+    const LEVEL: &str = "<main>\n<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response(CORRECT), Outcome::Correct);
+        assert_eq!(parse_response(TOO_HIGH), Outcome::TooHigh);
+        assert_eq!(
+            parse_response(&TOO_HIGH.replace("too high", "too low")),
+            Outcome::TooLow
+        );
+        assert_eq!(parse_response(WAIT), Outcome::Wait(Some(65)));
+        assert_eq!(parse_response(LEVEL), Outcome::WrongLevel);
+        assert_eq!(
+            parse_response("<article><p>Something <b>else</b></p></article>"),
+            Outcome::Unknown("Something else".to_string())
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, server) = serve(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let dir = TempDir::new("submit");
+        let path = dir.join("submissions.txt");
+        let mut log = SubmissionLog::load(&path).unwrap();
+        let mut client = Downloader::new(&url, "secret", "me@example.com");
+        client.interval = std::time::Duration::ZERO;
+
+        let high = Answer::from(500u32);
+        let outcome = client.submit(&mut log, 2023, 1, Part::Part1, &high);
+        assert_eq!(outcome, Ok(Outcome::TooHigh));
+        // Neither the same answer nor a higher one goes out again
+        assert!(client
+            .submit(&mut log, 2023, 1, Part::Part1, &high)
+            .is_err());
+        let higher = Answer::from(600u32);
+        assert!(client
+            .submit(&mut log, 2023, 1, Part::Part1, &higher)
+            .is_err());
+        let right = Answer::from(42u32);
+        let outcome = client.submit(&mut log, 2023, 1, Part::Part1, &right);
+        assert_eq!(outcome, Ok(Outcome::Correct));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/1/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=secret\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=500"));
+
+        // The log survives a reload
+        let log = SubmissionLog::load(&path).unwrap();
+        assert_eq!(log.submissions.len(), 2);
+        assert_eq!(log.submissions[1].outcome, Outcome::Correct);
+        assert!(log.refuse(1, Part::Part1, &Answer::from(1u32)).is_some());
+        assert!(log.refuse(1, Part::Part2, &Answer::from(1u32)).is_none());
+    }
+}
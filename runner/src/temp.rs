@@ -0,0 +1,34 @@
+use std::env;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// A fresh directory under the system temp dir for one test, removed again
+/// when it goes out of scope, failed asserts included.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// `aoc-<name>-<pid>`, emptied first in case a crashed run with the same
+    /// pid left it behind.
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("aoc-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
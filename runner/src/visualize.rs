@@ -0,0 +1,80 @@
+use std::env;
+use std::fmt::Display;
+use std::io::{self, IsTerminal};
+
+/// A text style for `Painter::paint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Bold,
+    Dim,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Style {
+    fn code(self) -> u8 {
+        match self {
+            Style::Bold => 1,
+            Style::Dim => 2,
+            Style::Red => 31,
+            Style::Green => 32,
+            Style::Yellow => 33,
+            Style::Blue => 34,
+            Style::Magenta => 35,
+            Style::Cyan => 36,
+        }
+    }
+}
+
+/// Styles `--visualize` output with ANSI escapes, or leaves it plain when
+/// they would end up in a file or pipe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Painter {
+    pub ansi: bool,
+}
+
+impl Painter {
+    /// ANSI when stdout is a terminal and `$NO_COLOR` is not set.
+    pub fn detect() -> Self {
+        Painter::detect_on(io::stdout())
+    }
+
+    /// `detect`, for output that goes to `stream` instead.
+    pub fn detect_on(stream: impl IsTerminal) -> Self {
+        Painter {
+            ansi: stream.is_terminal() && env::var_os("NO_COLOR").is_none(),
+        }
+    }
+
+    pub fn paint(&self, text: impl Display, style: Style) -> String {
+        match self.ansi {
+            true => format!("\x1b[{}m{}\x1b[0m", style.code(), text),
+            false => text.to_string(),
+        }
+    }
+
+    /// `text` in `style`, if any.
+    pub fn paint_if(&self, text: impl Display, style: Option<Style>) -> String {
+        match style {
+            Some(style) => self.paint(text, style),
+            None => text.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paint() {
+        let ansi = Painter { ansi: true };
+        assert_eq!(ansi.paint("42", Style::Green), "\x1b[32m42\x1b[0m");
+        assert_eq!(ansi.paint_if(7, None), "7");
+        assert_eq!(Painter { ansi: false }.paint("42", Style::Green), "42");
+    }
+}
@@ -0,0 +1,190 @@
+use crate::bench::BenchOptions;
+use crate::crypt;
+use crate::error::Error;
+use crate::examples::example_day;
+use crate::scaffold::crate_dir;
+use crate::source::Source;
+use crate::{run, Args, Year};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL: Duration = Duration::from_millis(500);
+
+/// How a watched file gets into a run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    /// Read at runtime, a rerun picks it up.
+    Input,
+    /// Compiled in, needs a rebuild.
+    Build,
+}
+
+/// The input, examples and module of every selected day.
+fn watched_files(years: &[Year], args: &Args) -> Vec<(PathBuf, Kind)> {
+    let mut files = Vec::new();
+    let Some(day) = args.day else {
+        return files;
+    };
+    for year in years
+        .iter()
+        .filter(|y| args.year.is_none_or(|year| y.year == year))
+    {
+        match &args.input {
+            Some(path) if path != Path::new("-") => files.push((path.clone(), Kind::Input)),
+            Some(_) => {}
+            None => {
+                if let Source::File(path) = Source::day(year, day, args.data_dir.as_deref()) {
+                    files.push((crypt::encrypted_path(&path), Kind::Input));
+                    files.push((path, Kind::Input));
+                }
+            }
+        }
+        let Ok(root) = crate_dir(year) else {
+            continue;
+        };
+        files.push((root.join(format!("src/day{}.rs", day)), Kind::Build));
+        let examples = root.join("examples");
+        files.push((examples.join("answers.txt"), Kind::Build));
+        for entry in fs::read_dir(&examples).into_iter().flatten().flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.strip_suffix(".txt").and_then(example_day) == Some(day) {
+                files.push((entry.path(), Kind::Build));
+            }
+        }
+    }
+    files
+}
+
+/// Modification times, `None` for files that don't exist (yet).
+fn snapshot(files: &[(PathBuf, Kind)]) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    files
+        .iter()
+        .map(|(path, _)| {
+            (
+                path.clone(),
+                fs::metadata(path).and_then(|m| m.modified()).ok(),
+            )
+        })
+        .collect()
+}
+
+/// Runs `cargo build` for the running binary, in the profile it was built in.
+fn rebuild(years: &[Year]) -> Result<PathBuf, Error> {
+    let exe = env::current_exe().map_err(|e| Error::new(format!("No executable: {}", e)))?;
+    let name = exe
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .ok_or_else(|| Error::new("No executable name"))?;
+    let dir = years
+        .first()
+        .and_then(|y| crate_dir(y).ok())
+        .ok_or_else(|| Error::new("No crate to build"))?;
+    let mut command = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    command
+        .args(["build", "--workspace", "--bin", &name])
+        .current_dir(dir);
+    if exe.components().any(|c| c.as_os_str() == "release") {
+        command.arg("--release");
+    }
+    let status = command
+        .status()
+        .map_err(|e| Error::new(format!("Could not run cargo: {}", e)))?;
+    if !status.success() {
+        return Err(Error::new("Build failed"));
+    }
+    Ok(exe)
+}
+
+/// Replaces this process with the freshly built `exe`, same arguments.
+fn restart(exe: &Path) -> Error {
+    let mut command = Command::new(exe);
+    command.args(env::args_os().skip(1));
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        Error::new(format!("Could not restart: {}", command.exec()))
+    }
+    #[cfg(not(unix))]
+    match command.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(e) => Error::new(format!("Could not restart: {}", e)),
+    }
+}
+
+/// `--watch`: prints the results and the day's examples, then again
+/// whenever one of the watched files changes.
+pub fn watch(years: &[Year], args: &Args) -> ExitCode {
+    let files = watched_files(years, args);
+    let mut seen = snapshot(&files);
+    let terminal = io::stdout().is_terminal();
+    // A failed build stays on screen until it is fixed
+    let mut broken = false;
+    let mut stale = false;
+    loop {
+        if !broken {
+            if terminal {
+                // Clear the screen and move to the top
+                print!("\x1b[2J\x1b[H");
+            } else {
+                println!("{:=<80}", "");
+            }
+            run(years, args);
+            if !args.example {
+                println!();
+                let examples = Args {
+                    example: true,
+                    input: None,
+                    bench: BenchOptions {
+                        bench: false,
+                        ..args.bench.clone()
+                    },
+                    compare: None,
+                    ..args.clone()
+                };
+                run(years, &examples);
+            }
+            if stale {
+                println!("\nSource or examples changed, restart to pick them up");
+            }
+            println!("\nWatching {} files, Ctrl-C to stop", files.len());
+            io::stdout().flush().ok();
+        }
+
+        let changed: Vec<Kind> = loop {
+            thread::sleep(POLL);
+            let now = snapshot(&files);
+            if now != seen {
+                let changed = files
+                    .iter()
+                    .filter(|(path, _)| now.get(path) != seen.get(path))
+                    .map(|&(_, kind)| kind)
+                    .collect();
+                seen = now;
+                break changed;
+            }
+        };
+        if !broken && !changed.contains(&Kind::Build) {
+            continue;
+        }
+        if args.no_rebuild {
+            stale = true;
+            continue;
+        }
+        match rebuild(years) {
+            Ok(exe) => {
+                eprintln!("{}", restart(&exe));
+                return ExitCode::FAILURE;
+            }
+            Err(e) => {
+                eprintln!("{}, waiting for changes", e);
+                broken = true;
+            }
+        }
+    }
+}